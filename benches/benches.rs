@@ -73,6 +73,12 @@ fn unmarshal(c: &mut Criterion) {
     });
 }
 
+fn is_valid(c: &mut Criterion) {
+    c.bench_function("is_valid", |b| {
+        b.iter(|| Ulid::is_valid(black_box("01ARYZ6S410000000000000000")))
+    });
+}
+
 fn timestamp(c: &mut Criterion) {
     let ulid = Ulid::new(Utc::now().timestamp() as u64, rand::random);
     c.bench_function("timestamp", |b| b.iter(|| ulid.timestamp()));
@@ -89,6 +95,38 @@ criterion_group!(
     marshal,
     marshal_to_string,
     unmarshal,
+    is_valid,
     timestamp,
 );
+
+#[cfg(feature = "getrandom")]
+fn generate(c: &mut Criterion) {
+    c.bench_function("generate", |b| b.iter(Ulid::generate));
+}
+
+#[cfg(feature = "getrandom")]
+fn generator_generate(c: &mut Criterion) {
+    let mut generator = ulid_rs::Generator::new();
+    c.bench_function("generator_generate", |b| {
+        b.iter(|| generator.generate().unwrap())
+    });
+}
+
+/// reads from the operating system for every Ulid, as `generate` did before
+/// the entropy was buffered
+#[cfg(feature = "getrandom")]
+fn generate_unbuffered(c: &mut Criterion) {
+    let mut entropy =
+        ulid_rs::BufferedEntropy::from_fn(10, |buf: &mut [u8]| getrandom::fill(buf).unwrap());
+    c.bench_function("generate_unbuffered", |b| {
+        b.iter(|| Ulid::now(&mut entropy))
+    });
+}
+
+#[cfg(feature = "getrandom")]
+criterion_group!(os, generate, generator_generate, generate_unbuffered);
+
+#[cfg(feature = "getrandom")]
+criterion_main!(benches, os);
+#[cfg(not(feature = "getrandom"))]
 criterion_main!(benches);
@@ -0,0 +1,97 @@
+//! `#[derive(UlidId)]`, for newtypes over a `ulid_rs::Ulid`
+//!
+//! Use it through the `derive` feature of ulid-rs, which re-exports it.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields};
+
+/// implements `FromStr`, `Display`, `AsRef<Ulid>` and `From` conversions both
+/// ways for a struct with a single `Ulid` field, along with serde and the
+/// database traits for each of those features enabled on ulid-rs
+///
+/// ```ignore
+/// #[derive(Clone, Copy, Debug, PartialEq, Eq, UlidId)]
+/// struct OrderId(Ulid);
+/// ```
+#[proc_macro_derive(UlidId)]
+pub fn derive_ulid_id(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "UlidId does not support generic types, use ulid_rs::Id<T> instead",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(s) => &s.fields,
+        _ => {
+            return Err(Error::new_spanned(
+                name,
+                "UlidId can only be derived for structs",
+            ))
+        }
+    };
+
+    if fields.len() != 1 {
+        return Err(Error::new_spanned(
+            fields,
+            "UlidId needs a struct with exactly one field, holding the Ulid",
+        ));
+    }
+
+    let (field, construct) = match fields {
+        Fields::Named(f) => {
+            let ident = f.named[0].ident.as_ref().unwrap();
+            (quote!(#ident), quote!(#name { #ident: ulid }))
+        }
+        _ => (quote!(0), quote!(#name(ulid))),
+    };
+
+    Ok(quote! {
+        impl ::core::convert::AsRef<::ulid_rs::Ulid> for #name {
+            fn as_ref(&self) -> &::ulid_rs::Ulid {
+                &self.#field
+            }
+        }
+
+        impl ::core::convert::From<::ulid_rs::Ulid> for #name {
+            fn from(ulid: ::ulid_rs::Ulid) -> Self {
+                #construct
+            }
+        }
+
+        impl ::core::convert::From<#name> for ::ulid_rs::Ulid {
+            fn from(id: #name) -> Self {
+                id.#field
+            }
+        }
+
+        impl ::core::str::FromStr for #name {
+            type Err = ::ulid_rs::UlidError;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                ::ulid_rs::Ulid::unmarshal(s).map(::core::convert::From::from)
+            }
+        }
+
+        impl ::core::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&self.#field, f)
+            }
+        }
+
+        ::ulid_rs::__ulid_id_impls!(#name);
+    })
+}
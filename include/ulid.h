@@ -0,0 +1,69 @@
+/*
+ * C bindings for ulid-rs, available with the `ffi` cargo feature.
+ *
+ * Generated by cbindgen from src/integrations/ffi.rs, do not edit. CI compiles
+ * tests/ffi.c against this header and links it with the library.
+ */
+
+#ifndef ULID_RS_H
+#define ULID_RS_H
+
+#include <stdint.h>
+
+/*
+ a Ulid as its 16 bytes, most significant first
+ */
+typedef uint8_t ulid_t[16];
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/*
+ writes a new Ulid for the current time, with entropy from the operating
+ system, into `out`
+
+ # Safety
+
+ `out` must be valid for writes of 16 bytes.
+ */
+void ulid_new(uint8_t *out);
+
+/*
+ writes the 26 character string form of `ulid` into `buf`, followed by a
+ NUL terminator
+
+ # Safety
+
+ `ulid` must be valid for reads of 16 bytes, and `buf` for writes of 27
+ bytes.
+ */
+void ulid_to_string(const uint8_t *ulid, char *buf);
+
+/*
+ parses the NUL terminated string `s` into `out`
+
+ Returns 0 on success, and -1 if `s` is not a valid Ulid, in which case
+ `out` is left untouched.
+
+ # Safety
+
+ `s` must point to a NUL terminated string, and `out` must be valid for
+ writes of 16 bytes.
+ */
+int ulid_parse(const char *s, uint8_t *out);
+
+/*
+ returns the timestamp of `ulid` in milliseconds
+
+ # Safety
+
+ `ulid` must be valid for reads of 16 bytes.
+ */
+uint64_t ulid_timestamp(const uint8_t *ulid);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ULID_RS_H */
@@ -0,0 +1,399 @@
+//! command line interface, built with the `cli` feature
+//!
+//! ```text
+//! ulid generate [-n <count>] [--monotonic]
+//! ulid inspect [--json] <id>
+//! ulid validate [--report] < ids
+//! ulid convert (--to uuid | --from uuid) [<id>...]
+//! ulid sort < ids
+//! ulid ts [--json] < ids
+//! ```
+
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process;
+
+use ulid_rs::{Generator, Ulid};
+
+const USAGE: &str = "usage:
+    ulid generate [-n <count>] [--monotonic]
+                                   print new Ulids for the current time, one per line,
+                                   strictly increasing with --monotonic
+    ulid inspect [--json] <id>     print the parts of a Ulid
+    ulid validate [--report]       print the valid Ulids read from stdin, or with --report
+                                   the line numbers of invalid ones and why
+    ulid convert (--to uuid | --from uuid) [<id>...]
+                                   convert between Ulids and UUIDs, reading stdin
+                                   when no ids are given
+    ulid sort                      sort the Ulids read from stdin
+    ulid ts [--json]               print each Ulid read from stdin with its RFC 3339
+                                   timestamp, as CSV rows or JSON lines with --json
+
+Timestamps past the year 9999 have no RFC 3339 form, and print as - in inspect,
+an empty CSV field in ts, and null with --json.";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("generate") => generate(&args[1..]),
+        Some("inspect") => inspect(&args[1..]),
+        Some("validate") => validate(&args[1..]),
+        Some("convert") => convert(&args[1..]),
+        Some("sort") => sort(&args[1..]),
+        Some("ts") => ts(&args[1..]),
+        Some("-h") | Some("--help") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some(command) => Err(format!("unknown command '{}'", command)),
+        None => Err("missing command".to_string()),
+    };
+
+    if let Err(e) = result {
+        eprintln!("ulid: {}\n\n{}", e, USAGE);
+        process::exit(2);
+    }
+}
+
+fn generate(args: &[String]) -> Result<(), String> {
+    let mut count = 1u64;
+    let mut monotonic = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-n" | "--count" => {
+                let value = args.next().ok_or("missing value for -n")?;
+                count = value
+                    .parse()
+                    .map_err(|_| format!("invalid count '{}'", value))?;
+            }
+            "--monotonic" => monotonic = true,
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut generator = Generator::new();
+
+    for _ in 0..count {
+        let ulid = if monotonic {
+            generator.generate().map_err(|e| e.to_string())?
+        } else {
+            Ulid::generate()
+        };
+
+        write_line(&mut out, ulid)?;
+    }
+
+    out.flush().or_else(ignore_broken_pipe)
+}
+
+fn inspect(args: &[String]) -> Result<(), String> {
+    let mut json = false;
+    let mut id = None;
+
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            _ if id.is_none() => id = Some(arg),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let id = id.ok_or("missing id")?;
+    let ulid = Ulid::unmarshal(id).map_err(|e| format!("invalid id '{}': {}", id, e))?;
+
+    let timestamp = ulid.timestamp();
+    let datetime = rfc3339(timestamp);
+    let entropy = &ulid.to_hex()[12..];
+    let uuid = ulid.to_uuid_string();
+
+    if json {
+        println!(
+            "{{\"ulid\":\"{}\",\"timestamp\":{},\"datetime\":{},\"entropy\":\"{}\",\"uuid\":\"{}\"}}",
+            ulid,
+            timestamp,
+            json_string(datetime.as_deref()),
+            entropy,
+            uuid
+        );
+    } else {
+        println!("ulid:      {}", ulid);
+        println!("timestamp: {}", timestamp);
+        println!("datetime:  {}", datetime.as_deref().unwrap_or("-"));
+        println!("entropy:   {}", entropy);
+        println!("uuid:      {}", uuid);
+    }
+
+    Ok(())
+}
+
+fn validate(args: &[String]) -> Result<(), String> {
+    let mut report = false;
+
+    for arg in args {
+        match arg.as_str() {
+            "--report" => report = true,
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut invalid = false;
+
+    // valid lines are printed as read, never re-encoded
+    each_line(|number, id| {
+        if id.is_empty() {
+            return Ok(());
+        }
+
+        match Ulid::unmarshal(id) {
+            Ok(_) if !report => write_line(&mut out, String::from_utf8_lossy(id)),
+            Err(e) if report => {
+                invalid = true;
+                write_line(
+                    &mut out,
+                    format_args!("line {}: {}: {}", number, e, String::from_utf8_lossy(id)),
+                )
+            }
+            _ => Ok(()),
+        }
+    })?;
+
+    out.flush().or_else(ignore_broken_pipe)?;
+
+    if invalid {
+        process::exit(1);
+    }
+
+    Ok(())
+}
+
+fn convert(args: &[String]) -> Result<(), String> {
+    let mut to_uuid = None;
+    let mut ids = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--to" | "--from" => {
+                match args.next().map(String::as_str) {
+                    Some("uuid") => {}
+                    Some(format) => return Err(format!("unknown format '{}'", format)),
+                    None => return Err(format!("missing format for {}", arg)),
+                }
+                to_uuid = Some(arg == "--to");
+            }
+            _ => ids.push(arg.as_bytes()),
+        }
+    }
+
+    let to_uuid = to_uuid.ok_or("missing --to uuid or --from uuid")?;
+    let convert = |id: &[u8]| {
+        let converted = if to_uuid {
+            Ulid::unmarshal(id).map(|ulid| ulid.to_uuid_string())
+        } else {
+            Ulid::parse_uuid(id).map(|ulid| ulid.to_string())
+        };
+
+        converted.map_err(|e| format!("invalid id '{}': {}", String::from_utf8_lossy(id), e))
+    };
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+
+    if ids.is_empty() {
+        each_line(|number, id| {
+            if id.is_empty() {
+                return Ok(());
+            }
+
+            let converted = convert(id).map_err(|e| format!("line {}: {}", number, e))?;
+            write_line(&mut out, converted)
+        })?;
+    } else {
+        for id in ids {
+            write_line(&mut out, convert(id)?)?;
+        }
+    }
+
+    out.flush().or_else(ignore_broken_pipe)
+}
+
+fn sort(args: &[String]) -> Result<(), String> {
+    if let Some(arg) = args.first() {
+        return Err(format!("unexpected argument '{}'", arg));
+    }
+
+    let mut ulids = Vec::new();
+    each_line(|number, id| {
+        if !id.is_empty() {
+            ulids.push(parse_line(number, id)?);
+        }
+
+        Ok(())
+    })?;
+
+    // the order of Ulid is the lexicographic order of the canonical strings
+    ulids.sort_unstable();
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+
+    for ulid in ulids {
+        write_line(&mut out, ulid)?;
+    }
+
+    out.flush().or_else(ignore_broken_pipe)
+}
+
+fn ts(args: &[String]) -> Result<(), String> {
+    let mut json = false;
+
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+
+    each_line(|number, id| {
+        if id.is_empty() {
+            return Ok(());
+        }
+
+        let ulid = parse_line(number, id)?;
+        let datetime = rfc3339(ulid.timestamp());
+
+        if json {
+            write_line(
+                &mut out,
+                format_args!(
+                    "{{\"ulid\":\"{}\",\"datetime\":{}}}",
+                    ulid,
+                    json_string(datetime.as_deref())
+                ),
+            )
+        } else {
+            write_line(
+                &mut out,
+                format_args!("{},{}", ulid, datetime.as_deref().unwrap_or("")),
+            )
+        }
+    })?;
+
+    out.flush().or_else(ignore_broken_pipe)
+}
+
+fn parse_line(number: u64, id: &[u8]) -> Result<Ulid, String> {
+    Ulid::unmarshal(id).map_err(|e| {
+        format!(
+            "line {}: invalid id '{}': {}",
+            number,
+            String::from_utf8_lossy(id),
+            e
+        )
+    })
+}
+
+/// calls `f` with the line number and the trimmed contents of each line on stdin
+fn each_line<F>(mut f: F) -> Result<(), String>
+where
+    F: FnMut(u64, &[u8]) -> Result<(), String>,
+{
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let mut line = Vec::new();
+    let mut number = 0;
+
+    loop {
+        line.clear();
+        if input
+            .read_until(b'\n', &mut line)
+            .map_err(|e| e.to_string())?
+            == 0
+        {
+            return Ok(());
+        }
+        number += 1;
+
+        f(number, trim(&line))?;
+    }
+}
+
+/// trims ASCII whitespace, including the line ending
+fn trim(mut s: &[u8]) -> &[u8] {
+    while let [first, rest @ ..] = s {
+        if !first.is_ascii_whitespace() {
+            break;
+        }
+        s = rest;
+    }
+
+    while let [rest @ .., last] = s {
+        if !last.is_ascii_whitespace() {
+            break;
+        }
+        s = rest;
+    }
+
+    s
+}
+
+fn write_line<W: Write, D: std::fmt::Display>(out: &mut W, line: D) -> Result<(), String> {
+    writeln!(out, "{}", line).or_else(ignore_broken_pipe)
+}
+
+/// a closed pipe, as with `ulid generate -n 1000 | head`, is not an error
+fn ignore_broken_pipe(e: io::Error) -> Result<(), String> {
+    if e.kind() == io::ErrorKind::BrokenPipe {
+        process::exit(0);
+    }
+
+    Err(e.to_string())
+}
+
+/// formats milliseconds since the unix epoch as an RFC 3339 UTC datetime, or
+/// returns `None` past the year 9999, which RFC 3339 can not represent
+fn rfc3339(millis: u64) -> Option<String> {
+    let secs = millis / 1000;
+    let days = (secs / 86400) as i64;
+    let secs_of_day = secs % 86400;
+
+    // civil date from days since 1970-01-01, after Howard Hinnant's algorithm
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    if year > 9999 {
+        return None;
+    }
+
+    Some(format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60,
+        millis % 1000
+    ))
+}
+
+/// formats `s` as a JSON string, or `null` for `None`
+fn json_string(s: Option<&str>) -> String {
+    s.map_or_else(|| "null".to_string(), |s| format!("\"{}\"", s))
+}
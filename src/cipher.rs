@@ -0,0 +1,52 @@
+//! encryption of Ulids with AES-128, for public identifiers that do not reveal
+//! when, or how many, Ulids were created
+
+use core::fmt;
+
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit};
+use aes::Aes128;
+
+use crate::Ulid;
+
+/// encrypts Ulids into other Ulids with a 128 bit key, and decrypts them back
+///
+/// A Ulid is a single AES block, so an encrypted Ulid is again a valid Ulid of
+/// the same length and alphabet, but with a timestamp and entropy that look
+/// random. Keep the plain Ulids for storage, where they sort by creation, and
+/// encrypt them only when exposing them. The same Ulid and key always give the
+/// same encrypted Ulid.
+#[derive(Clone)]
+pub struct UlidCipher {
+    cipher: Aes128,
+}
+
+impl UlidCipher {
+    /// creates new UlidCipher from a 128 bit key
+    pub fn new(key: [u8; 16]) -> UlidCipher {
+        UlidCipher {
+            cipher: Aes128::new(&GenericArray::from(key)),
+        }
+    }
+
+    /// encrypts a Ulid for exposing it
+    pub fn encrypt(&self, ulid: Ulid) -> Ulid {
+        let mut block = GenericArray::from(ulid.0);
+        self.cipher.encrypt_block(&mut block);
+        Ulid(block.into())
+    }
+
+    /// decrypts a Ulid from `encrypt` with the same key
+    pub fn decrypt(&self, ulid: Ulid) -> Ulid {
+        let mut block = GenericArray::from(ulid.0);
+        self.cipher.decrypt_block(&mut block);
+        Ulid(block.into())
+    }
+}
+
+impl fmt::Debug for UlidCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // leaves out the key schedule, from which the key can be recovered
+        f.debug_struct("UlidCipher").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,98 @@
+//! the current time, for the constructors that do not take a timestamp
+//!
+//! `std::time::SystemTime::now` panics on `wasm32-unknown-unknown`, so with the
+//! `wasm` feature enabled on that target the time comes from JavaScript's
+//! `Date.now` instead.
+
+/// a source for the current time, in milliseconds since the unix epoch
+///
+/// Implemented by `SystemClock`, and by closures returning a `u64`.
+pub trait Clock {
+    /// returns the milliseconds elapsed since the unix epoch
+    fn now_millis(&self) -> u64;
+}
+
+impl<F> Clock for F
+where
+    F: Fn() -> u64,
+{
+    fn now_millis(&self) -> u64 {
+        self()
+    }
+}
+
+/// the system clock, which is a `Clock` with `std`
+///
+/// Reads `Date.now` on `wasm32-unknown-unknown` with the `wasm` feature, and
+/// returns 0 if the system clock is set before the unix epoch.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now_millis(&self) -> u64 {
+        now_millis()
+    }
+}
+
+/// a `Clock` counting milliseconds from a custom epoch, given in milliseconds
+/// since the unix epoch, in place of the unix epoch
+///
+/// The 48 bit timestamp then lasts until 8919 years after the custom epoch.
+/// Ulids created with it are read back with `Ulid::timestamp_with_epoch`, and
+/// converted to unix epoch Ulids with `Ulid::rebase_epoch`. Returns 0 before
+/// the epoch.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EpochClock<C = SystemClock> {
+    clock: C,
+    epoch: u64,
+}
+
+impl EpochClock {
+    /// creates new EpochClock counting from `epoch` on the system clock
+    pub fn new(epoch: u64) -> EpochClock {
+        EpochClock::with_clock(epoch, SystemClock)
+    }
+}
+
+impl<C> EpochClock<C> {
+    /// creates new EpochClock counting from `epoch` on another clock
+    pub fn with_clock(epoch: u64, clock: C) -> EpochClock<C> {
+        EpochClock { clock, epoch }
+    }
+
+    /// returns the epoch, in milliseconds since the unix epoch
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+}
+
+impl<C> Clock for EpochClock<C>
+where
+    C: Clock,
+{
+    fn now_millis(&self) -> u64 {
+        self.clock.now_millis().saturating_sub(self.epoch)
+    }
+}
+
+/// returns the milliseconds elapsed since the unix epoch
+#[cfg(all(feature = "wasm", target_family = "wasm", target_os = "unknown"))]
+pub(crate) fn now_millis() -> u64 {
+    crate::integrations::wasm::date_now() as u64
+}
+
+/// returns the milliseconds elapsed since the unix epoch, or 0 if the system
+/// clock is set before it
+#[cfg(all(
+    feature = "std",
+    not(all(feature = "wasm", target_family = "wasm", target_os = "unknown"))
+))]
+pub(crate) fn now_millis() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,80 @@
+//! base58 encoding, using the Bitcoin alphabet
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::{Ulid, UlidError};
+
+/// Bitcoin base58
+#[cfg(feature = "alloc")]
+static BASE58: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+impl Ulid {
+    /// create a base58 representation of the stored ULID, using the Bitcoin
+    /// alphabet
+    ///
+    /// As in Bitcoin, every leading zero byte is encoded as a single `1`, so
+    /// the output is between 16 and 22 characters long.
+    #[cfg(feature = "alloc")]
+    pub fn to_base58(&self) -> String {
+        let zeros = self.0.iter().take_while(|&&b| b == 0).count();
+
+        let mut val = u128::from_be_bytes(self.0);
+        let mut digits = [0; 22];
+        let mut n = 0;
+
+        while val > 0 {
+            digits[n] = BASE58[(val % 58) as usize];
+            val /= 58;
+            n += 1;
+        }
+
+        let mut ans = String::with_capacity(zeros + n);
+        for _ in 0..zeros {
+            ans.push('1');
+        }
+        ans.extend(digits[..n].iter().rev().map(|&x| x as char));
+
+        ans
+    }
+
+    /// decodes a base58 string using the Bitcoin alphabet into a ULID
+    ///
+    /// The input must decode to exactly 16 bytes, leading `1`s included.
+    pub fn from_base58<S>(s: S) -> Result<Ulid, UlidError>
+    where
+        S: AsRef<[u8]>,
+    {
+        let s = s.as_ref();
+
+        let zeros = s.iter().take_while(|&&x| x == b'1').count();
+
+        let mut val: u128 = 0;
+        for &x in &s[zeros..] {
+            let word = Self::unmarshal_base58_word(x)? as u128;
+            val = val
+                .checked_mul(58)
+                .and_then(|v| v.checked_add(word))
+                .ok_or(UlidError::InvalidBase58Length)?;
+        }
+
+        let len = 16 - val.leading_zeros() as usize / 8;
+        if zeros + len != 16 {
+            return Err(UlidError::InvalidBase58Length);
+        }
+
+        Ok(Ulid(val.to_be_bytes()))
+    }
+
+    fn unmarshal_base58_word(x: u8) -> Result<u8, UlidError> {
+        match x {
+            b'1'..=b'9' => Ok(x - b'1'),
+            b'A'..=b'H' => Ok(x - b'A' + 9),
+            b'J'..=b'N' => Ok(x - b'J' + 17),
+            b'P'..=b'Z' => Ok(x - b'P' + 22),
+            b'a'..=b'k' => Ok(x - b'a' + 33),
+            b'm'..=b'z' => Ok(x - b'm' + 44),
+            _ => Err(UlidError::InvalidBase58Character),
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! unpadded base64url encoding
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::{Ulid, UlidError};
+
+/// URL and filename safe base64, RFC 4648 section 5
+#[cfg(feature = "alloc")]
+static BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+impl Ulid {
+    /// create a 22 character unpadded base64url representation of the stored ULID
+    #[cfg(feature = "alloc")]
+    pub fn to_base64url(&self) -> String {
+        let val = u128::from_be_bytes(self.0);
+        let mut ans = String::with_capacity(22);
+
+        for i in 0..21 {
+            ans.push(BASE64URL[((val >> (122 - 6 * i)) & 63) as usize] as char);
+        }
+
+        // the last character only carries the 2 remaining bits
+        ans.push(BASE64URL[((val & 3) << 4) as usize] as char);
+
+        ans
+    }
+
+    /// decodes a 22 character unpadded base64url string into a ULID
+    ///
+    /// The unused low 4 bits of the last character must be zero, so every
+    /// Ulid has exactly one accepted representation.
+    pub fn from_base64url<S>(s: S) -> Result<Ulid, UlidError>
+    where
+        S: AsRef<[u8]>,
+    {
+        let s = s.as_ref();
+
+        if s.len() != 22 {
+            return Err(UlidError::InvalidBase64Length);
+        }
+
+        let mut val: u128 = 0;
+        for &x in &s[..21] {
+            val = (val << 6) | Self::unmarshal_base64url_word(x)? as u128;
+        }
+
+        let last = Self::unmarshal_base64url_word(s[21])?;
+        if last & 15 != 0 {
+            return Err(UlidError::InvalidBase64Character);
+        }
+
+        val = (val << 2) | (last >> 4) as u128;
+
+        Ok(Ulid(val.to_be_bytes()))
+    }
+
+    fn unmarshal_base64url_word(x: u8) -> Result<u8, UlidError> {
+        match x {
+            b'A'..=b'Z' => Ok(x - b'A'),
+            b'a'..=b'z' => Ok(x - b'a' + 26),
+            b'0'..=b'9' => Ok(x - b'0' + 52),
+            b'-' => Ok(62),
+            b'_' => Ok(63),
+            _ => Err(UlidError::InvalidBase64Character),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! the 27 character form with a Crockford check symbol, to catch IDs that were
+//! typed in by hand
+
+use crate::{Ulid, UlidError};
+
+/// the check symbols for the values 0 to 36, the base32 alphabet followed by
+/// the 5 symbols used only for checks
+static CHECK_SYMBOLS: &[u8; 37] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ*~$=U";
+
+impl Ulid {
+    /// the 26 characters followed by a check symbol for the Ulid modulo 37
+    ///
+    /// `unmarshal_checked` then detects any single mistyped character, and any
+    /// two adjacent characters swapped.
+    pub fn marshal_checked(&self) -> [u8; 27] {
+        let mut ans = [0; 27];
+        ans[..26].copy_from_slice(&self.marshal());
+        ans[26] = self.check_symbol();
+        ans
+    }
+
+    /// parses the 27 character form from `marshal_checked`, failing with
+    /// `UlidError::InvalidCheckSymbol` when the check symbol does not match
+    pub fn unmarshal_checked<S>(s: S) -> Result<Ulid, UlidError>
+    where
+        S: AsRef<[u8]>,
+    {
+        let s = s.as_ref();
+
+        if s.len() != 27 {
+            return Err(UlidError::InvalidLength { len: s.len() });
+        }
+
+        let ans = Ulid::unmarshal(&s[..26])?;
+
+        if !CHECK_SYMBOLS.contains(&s[26]) {
+            return Err(UlidError::InvalidCharacter {
+                index: 26,
+                byte: s[26],
+            });
+        }
+
+        if s[26] != ans.check_symbol() {
+            return Err(UlidError::InvalidCheckSymbol);
+        }
+
+        Ok(ans)
+    }
+
+    fn check_symbol(&self) -> u8 {
+        CHECK_SYMBOLS[(u128::from(*self) % 37) as usize]
+    }
+}
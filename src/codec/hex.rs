@@ -0,0 +1,131 @@
+//! hex encoding, including the hyphenated UUID form
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::fmt;
+
+use crate::{Ulid, UlidError};
+
+/// lowercase hexadecimal
+#[cfg(feature = "alloc")]
+static HEX: &[u8; 16] = b"0123456789abcdef";
+
+impl Ulid {
+    /// create a 32 character lowercase hex representation of the stored ULID
+    #[cfg(feature = "alloc")]
+    pub fn to_hex(&self) -> String {
+        let mut ans = String::with_capacity(32);
+
+        for b in self.0.iter() {
+            ans.push(HEX[(b >> 4) as usize] as char);
+            ans.push(HEX[(b & 15) as usize] as char);
+        }
+
+        ans
+    }
+
+    /// decodes 32 hex digits, in either case, into a ULID
+    pub fn from_hex<S>(s: S) -> Result<Ulid, UlidError>
+    where
+        S: AsRef<[u8]>,
+    {
+        let s = s.as_ref();
+
+        if s.len() != 32 {
+            return Err(UlidError::InvalidHexLength);
+        }
+
+        let mut val = [0; 16];
+        for (i, v) in val.iter_mut().enumerate() {
+            *v = (Self::unmarshal_hex_word(s[2 * i])? << 4)
+                | Self::unmarshal_hex_word(s[2 * i + 1])?;
+        }
+
+        Ok(Ulid(val))
+    }
+
+    /// create the hyphenated 8-4-4-4-12 lowercase hex representation of the
+    /// stored ULID, the way UUIDs are displayed
+    #[cfg(feature = "alloc")]
+    pub fn to_uuid_string(&self) -> String {
+        let mut ans = String::with_capacity(36);
+
+        for (i, b) in self.0.iter().enumerate() {
+            if i == 4 || i == 6 || i == 8 || i == 10 {
+                ans.push('-');
+            }
+
+            ans.push(HEX[(b >> 4) as usize] as char);
+            ans.push(HEX[(b & 15) as usize] as char);
+        }
+
+        ans
+    }
+
+    /// parses a UUID formatted string into a ULID with the same 128 bits
+    ///
+    /// Accepts the hyphenated 8-4-4-4-12 form as well as the 32 hex digits
+    /// without hyphens, in either case. Fails with the hex errors of
+    /// `Ulid::from_hex`, with a misplaced hyphen being an invalid hex digit.
+    pub fn parse_uuid<S>(s: S) -> Result<Ulid, UlidError>
+    where
+        S: AsRef<[u8]>,
+    {
+        let s = s.as_ref();
+
+        match s.len() {
+            32 => Self::from_hex(s),
+            36 => {
+                let mut digits = [0; 32];
+                let mut n = 0;
+
+                for (i, &x) in s.iter().enumerate() {
+                    if i == 8 || i == 13 || i == 18 || i == 23 {
+                        if x != b'-' {
+                            return Err(UlidError::InvalidHexCharacter);
+                        }
+                    } else {
+                        digits[n] = x;
+                        n += 1;
+                    }
+                }
+
+                Self::from_hex(digits)
+            }
+            _ => Err(UlidError::InvalidHexLength),
+        }
+    }
+
+    fn unmarshal_hex_word(x: u8) -> Result<u8, UlidError> {
+        match x {
+            b'0'..=b'9' => Ok(x - b'0'),
+            b'a'..=b'f' => Ok(x - b'a' + 10),
+            b'A'..=b'F' => Ok(x - b'A' + 10),
+            _ => Err(UlidError::InvalidHexCharacter),
+        }
+    }
+}
+
+/// formats the 128 bits the way `u128` does, so `{:032x}` gives the zero
+/// padded form
+impl fmt::LowerHex for Ulid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&u128::from_be_bytes(self.0), f)
+    }
+}
+
+/// formats the 128 bits the way `u128` does, so `{:032X}` gives the zero
+/// padded form
+impl fmt::UpperHex for Ulid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperHex::fmt(&u128::from_be_bytes(self.0), f)
+    }
+}
+
+/// formats the 128 bits the way `u128` does, so `{:0128b}` gives the zero
+/// padded form
+impl fmt::Binary for Ulid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Binary::fmt(&u128::from_be_bytes(self.0), f)
+    }
+}
@@ -0,0 +1,66 @@
+//! parsing of delimited Ulids from a large buffer
+
+use crate::{Ulid, UlidError};
+
+/// an iterator over the Ulids in a delimited buffer, created by
+/// `Ulid::parse_many`
+///
+/// Yields the byte offset where each one starts along with the result of
+/// parsing it, so failures can be reported by position, and adding the offset
+/// to the index of an `InvalidCharacter` gives the position in the buffer.
+#[derive(Clone, Debug)]
+pub struct ParseMany<'a> {
+    buf: &'a [u8],
+    delimiter: u8,
+    offset: usize,
+}
+
+impl Ulid {
+    /// parses the Ulids in `buf` separated by `delimiter`, such as newline or
+    /// comma delimited exports, without allocating
+    ///
+    /// Surrounding ASCII whitespace, including the `\r` of CRLF line endings,
+    /// is ignored, as are empty fields.
+    pub fn parse_many(buf: &[u8], delimiter: u8) -> ParseMany<'_> {
+        ParseMany {
+            buf,
+            delimiter,
+            offset: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for ParseMany<'a> {
+    type Item = (usize, Result<Ulid, UlidError>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.buf.is_empty() {
+            let end = self
+                .buf
+                .iter()
+                .position(|&b| b == self.delimiter)
+                .unwrap_or(self.buf.len());
+            let (field, rest) = self.buf.split_at(end);
+            let offset = self.offset;
+
+            self.buf = rest.get(1..).unwrap_or_default();
+            self.offset += end + 1;
+
+            let start = match field.iter().position(|b| !b.is_ascii_whitespace()) {
+                Some(start) => start,
+                None => continue,
+            };
+            let len = field
+                .iter()
+                .rposition(|b| !b.is_ascii_whitespace())
+                .unwrap()
+                + 1;
+
+            return Some((offset + start, Ulid::unmarshal(&field[start..len])));
+        }
+
+        None
+    }
+}
+
+impl core::iter::FusedIterator for ParseMany<'_> {}
@@ -0,0 +1,295 @@
+//! Crockford base32, the canonical text form, along with the other text
+//! encodings
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::convert::TryFrom;
+#[cfg(feature = "alloc")]
+use core::convert::TryInto;
+use core::fmt;
+
+use crate::{Ulid, UlidError};
+
+mod base58;
+mod base64;
+mod check;
+mod hex;
+mod many;
+mod redacted;
+pub(crate) mod simd;
+mod strictness;
+
+pub use many::ParseMany;
+pub use redacted::Redacted;
+pub use strictness::Strictness;
+
+/// Crockford's base32
+pub(crate) static ENCODING: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// stores decimal encodings for characters.
+pub(crate) static DECODING: &[u8; 256] = &[
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    /* 0     1     2     3     4     5     6     7  */
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+    /* 8     9                                      */
+    0x08, 0x09, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    /*    10(A) 11(B) 12(C) 13(D) 14(E) 15(F) 16(G) */
+    0xFF, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F, 0x10,
+    /*17(H)     18(J) 19(K)       20(M) 21(N)       */
+    0x11, 0xFF, 0x12, 0x13, 0xFF, 0x14, 0x15, 0xFF,
+    /*22(P)23(Q)24(R) 25(S) 26(T)       27(V) 28(W) */
+    0x16, 0x17, 0x18, 0x19, 0x1A, 0xFF, 0x1B, 0x1C,
+    /*29(X)30(Y)31(Z)                               */
+    0x1D, 0x1E, 0x1F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+];
+
+impl Ulid {
+    /// create a string representation of the stored ULID
+    ///
+    /// Uses SSSE3, AVX2 or NEON when the CPU supports them.
+    pub fn marshal(&self) -> [u8; 26] {
+        simd::marshal(&self.0).unwrap_or_else(|| self.marshal_scalar())
+    }
+
+    /// writes the 26 characters into any `fmt::Write`, such as a fixed buffer
+    /// on targets without an allocator, as `Display` does
+    pub fn write_to<W>(&self, w: &mut W) -> fmt::Result
+    where
+        W: fmt::Write + ?Sized,
+    {
+        // marshal only ever produces bytes from ENCODING, which is ASCII
+        w.write_str(core::str::from_utf8(&self.marshal()).unwrap())
+    }
+
+    /// https://github.com/suyash/val/blob/master/ulid_uint128.hh#L253
+    pub(crate) fn marshal_scalar(&self) -> [u8; 26] {
+        let mut ans = [0; 26];
+        let val = self.0;
+
+        // timestamp
+        ans[0] = ENCODING[((val[0] & 224) >> 5) as usize];
+        ans[1] = ENCODING[(val[0] & 31) as usize];
+        ans[2] = ENCODING[((val[1] & 248) >> 3) as usize];
+        ans[3] = ENCODING[(((val[1] & 7) << 2) | ((val[2] & 192) >> 6)) as usize];
+        ans[4] = ENCODING[((val[2] & 62) >> 1) as usize];
+        ans[5] = ENCODING[(((val[2] & 1) << 4) | ((val[3] & 240) >> 4)) as usize];
+        ans[6] = ENCODING[(((val[3] & 15) << 1) | ((val[4] & 128) >> 7)) as usize];
+        ans[7] = ENCODING[((val[4] & 124) >> 2) as usize];
+        ans[8] = ENCODING[(((val[4] & 3) << 3) | ((val[5] & 224) >> 5)) as usize];
+        ans[9] = ENCODING[(val[5] & 31) as usize];
+
+        // entropy
+        ans[10] = ENCODING[((val[6] & 248) >> 3) as usize];
+        ans[11] = ENCODING[(((val[6] & 7) << 2) | ((val[7] & 192) >> 6)) as usize];
+        ans[12] = ENCODING[((val[7] & 62) >> 1) as usize];
+        ans[13] = ENCODING[(((val[7] & 1) << 4) | ((val[8] & 240) >> 4)) as usize];
+        ans[14] = ENCODING[(((val[8] & 15) << 1) | ((val[9] & 128) >> 7)) as usize];
+        ans[15] = ENCODING[((val[9] & 124) >> 2) as usize];
+        ans[16] = ENCODING[(((val[9] & 3) << 3) | ((val[10] & 224) >> 5)) as usize];
+        ans[17] = ENCODING[(val[10] & 31) as usize];
+        ans[18] = ENCODING[((val[11] & 248) >> 3) as usize];
+        ans[19] = ENCODING[(((val[11] & 7) << 2) | ((val[12] & 192) >> 6)) as usize];
+        ans[20] = ENCODING[((val[12] & 62) >> 1) as usize];
+        ans[21] = ENCODING[(((val[12] & 1) << 4) | ((val[13] & 240) >> 4)) as usize];
+        ans[22] = ENCODING[(((val[13] & 15) << 1) | ((val[14] & 128) >> 7)) as usize];
+        ans[23] = ENCODING[((val[14] & 124) >> 2) as usize];
+        ans[24] = ENCODING[(((val[14] & 3) << 3) | ((val[15] & 224) >> 5)) as usize];
+        ans[25] = ENCODING[(val[15] & 31) as usize];
+
+        ans
+    }
+
+    /// returns the first 10 characters of the string representation, which
+    /// encode the timestamp
+    ///
+    /// Every Ulid of the same millisecond shares it, for prefix listing by time.
+    pub fn timestamp_prefix(&self) -> [u8; 10] {
+        Ulid::prefix_for_timestamp(self.timestamp())
+    }
+
+    /// returns the 10 character prefix shared by the string representation of
+    /// every Ulid with the timestamp, see `timestamp_prefix`
+    ///
+    /// Only the last 48 bits of the timestamp are kept.
+    pub fn prefix_for_timestamp(timestamp: u64) -> [u8; 10] {
+        let timestamp = timestamp & ((1 << 48) - 1);
+        let mut ans = [0; 10];
+
+        for (i, c) in ans.iter_mut().enumerate() {
+            *c = ENCODING[(timestamp >> (45 - 5 * i)) as usize & 31];
+        }
+
+        ans
+    }
+
+    /// decodes only the timestamp from the string representation, or from its
+    /// 10 character prefix, without decoding the entropy
+    ///
+    /// Rejects a first character above `7`, which would overflow 48 bits.
+    pub fn timestamp_from_str<S>(s: S) -> Result<u64, UlidError>
+    where
+        S: AsRef<[u8]>,
+    {
+        let s = s.as_ref();
+
+        if s.len() != 26 && s.len() != 10 {
+            return Err(UlidError::InvalidLength { len: s.len() });
+        }
+
+        let mut ans = 0;
+        for (index, &byte) in s[..10].iter().enumerate() {
+            let word = DECODING[byte as usize];
+
+            if word == 0xFF || (index == 0 && word > 7) {
+                return Err(UlidError::InvalidCharacter { index, byte });
+            }
+
+            ans = ans << 5 | u64::from(word);
+        }
+
+        Ok(ans)
+    }
+
+    /// unmarshals a string-like into a ULID
+    ///
+    /// Fails with `UlidError::InvalidCharacter` at index 0 for a first character
+    /// above `7`, which would overflow 128 bits. Uses SSSE3, AVX2 or NEON when
+    /// the CPU supports them.
+    pub fn unmarshal<S>(s: S) -> Result<Ulid, UlidError>
+    where
+        S: AsRef<[u8]>,
+    {
+        let s = s.as_ref();
+        let s = <&[u8; 26]>::try_from(s).map_err(|_| UlidError::InvalidLength { len: s.len() })?;
+
+        match simd::unmarshal(s) {
+            Some(Some(ulid)) => Ok(ulid),
+            // invalid input is decoded again to find the first invalid character
+            _ => Self::unmarshal_scalar(s),
+        }
+    }
+
+    /// checks that a string-like is a valid ULID, without decoding it
+    ///
+    /// Checks the length, the alphabet and the first character as `unmarshal`
+    /// does.
+    pub fn is_valid<S>(s: S) -> bool
+    where
+        S: AsRef<[u8]>,
+    {
+        let s = s.as_ref();
+
+        s.len() == 26 && s[0] <= b'7' && s.iter().all(|&x| DECODING[x as usize] != 0xFF)
+    }
+
+    pub(crate) fn unmarshal_scalar(s: &[u8; 26]) -> Result<Ulid, UlidError> {
+        let mut w = [0; 26];
+        for (index, (&byte, word)) in s.iter().zip(w.iter_mut()).enumerate() {
+            *word = DECODING[byte as usize];
+            if *word == 0xFF || (index == 0 && *word > 7) {
+                return Err(UlidError::InvalidCharacter { index, byte });
+            }
+        }
+
+        let mut val = [0; 16];
+
+        // timestamp
+        val[0] = (w[0] << 5) | w[1];
+        val[1] = (w[2] << 3) | (w[3] >> 2);
+        val[2] = (w[3] << 6) | (w[4] << 1) | (w[5] >> 4);
+        val[3] = (w[5] << 4) | (w[6] >> 1);
+        val[4] = (w[6] << 7) | (w[7] << 2) | (w[8] >> 3);
+        val[5] = (w[8] << 5) | w[9];
+
+        // entropy
+        val[6] = (w[10] << 3) | (w[11] >> 2);
+        val[7] = (w[11] << 6) | (w[12] << 1) | (w[13] >> 4);
+        val[8] = (w[13] << 4) | (w[14] >> 1);
+        val[9] = (w[14] << 7) | (w[15] << 2) | (w[16] >> 3);
+        val[10] = (w[16] << 5) | w[17];
+        val[11] = (w[18] << 3) | (w[19] >> 2);
+        val[12] = (w[19] << 6) | (w[20] << 1) | (w[21] >> 4);
+        val[13] = (w[21] << 4) | (w[22] >> 1);
+        val[14] = (w[22] << 7) | (w[23] << 2) | (w[24] >> 3);
+        val[15] = (w[24] << 5) | w[25];
+
+        Ok(Ulid(val))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<String> for Ulid {
+    type Error = UlidError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Ulid::unmarshal(s)
+    }
+}
+
+impl TryFrom<&str> for Ulid {
+    type Error = UlidError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Ulid::unmarshal(s)
+    }
+}
+
+impl core::str::FromStr for Ulid {
+    type Err = UlidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ulid::unmarshal(s)
+    }
+}
+
+/// equal when the string parses to the Ulid, as with `unmarshal`
+impl PartialEq<str> for Ulid {
+    fn eq(&self, other: &str) -> bool {
+        matches!(Ulid::unmarshal(other), Ok(u) if u == *self)
+    }
+}
+
+impl PartialEq<&str> for Ulid {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl PartialEq<Ulid> for str {
+    fn eq(&self, other: &Ulid) -> bool {
+        *other == *self
+    }
+}
+
+impl PartialEq<Ulid> for &str {
+    fn eq(&self, other: &Ulid) -> bool {
+        *other == **self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryInto<String> for Ulid {
+    type Error = alloc::string::FromUtf8Error;
+
+    fn try_into(self) -> Result<String, Self::Error> {
+        String::from_utf8(self.marshal().to_vec())
+    }
+}
+
+impl fmt::Display for Ulid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_to(f)
+    }
+}
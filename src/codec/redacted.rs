@@ -0,0 +1,37 @@
+//! a display form for logs, with the entropy masked
+
+use core::fmt;
+
+use crate::Ulid;
+
+impl Ulid {
+    /// displays the 10 timestamp characters and masks the 16 entropy
+    /// characters, as in `01ARYZ6S41-****************`
+    ///
+    /// Logs then keep when the Ulid was created, without the full ID that
+    /// could be replayed or confirmed by guessing.
+    pub fn display_redacted(&self) -> Redacted {
+        Redacted(*self)
+    }
+}
+
+/// a Ulid displayed with its entropy masked, from `Ulid::display_redacted`
+///
+/// Formats the same with `Debug`, so it stays redacted in derived `Debug`
+/// output of structs holding it.
+#[derive(Clone, Copy)]
+pub struct Redacted(Ulid);
+
+impl fmt::Display for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the prefix only ever holds characters from ENCODING, which is ASCII
+        f.write_str(core::str::from_utf8(&self.0.timestamp_prefix()).unwrap())?;
+        f.write_str("-****************")
+    }
+}
+
+impl fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
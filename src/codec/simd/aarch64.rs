@@ -0,0 +1,116 @@
+//! NEON
+
+use core::arch::aarch64::*;
+
+use super::{SHIFTS, WINDOWS};
+use crate::codec::{DECODING, ENCODING};
+use crate::Ulid;
+
+/// picks the last 16 of the 20 bytes in four 40 bit lanes
+static GATHER: [u8; 16] = [0, 12, 11, 10, 9, 8, 20, 19, 18, 17, 16, 28, 27, 26, 25, 24];
+
+/// encodes 16 bytes into 26 characters
+///
+/// # Safety
+///
+/// the CPU must support NEON
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn marshal_neon(val: &[u8; 16]) -> [u8; 26] {
+    let input = vld1q_u8(val.as_ptr());
+
+    let mut lanes = [vdupq_n_u16(0); 4];
+    for (i, lane) in lanes.iter_mut().enumerate() {
+        // out of range indices produce zeros
+        let window = vreinterpretq_u16_u8(vqtbl1q_u8(input, vld1q_u8(WINDOWS[16 * i..].as_ptr())));
+        let shifts = vreinterpretq_s16_u16(vld1q_u16(SHIFTS[8 * i..].as_ptr()));
+
+        *lane = vshrq_n_u16(vshlq_u16(window, shifts), 11);
+    }
+
+    let alphabet = uint8x16x2_t(
+        vld1q_u8(ENCODING.as_ptr()),
+        vld1q_u8(ENCODING[16..].as_ptr()),
+    );
+
+    let mut ans = [0; 32];
+    vst1q_u8(
+        ans.as_mut_ptr(),
+        vqtbl2q_u8(
+            alphabet,
+            vcombine_u8(vmovn_u16(lanes[0]), vmovn_u16(lanes[1])),
+        ),
+    );
+    vst1q_u8(
+        ans[16..].as_mut_ptr(),
+        vqtbl2q_u8(
+            alphabet,
+            vcombine_u8(vmovn_u16(lanes[2]), vmovn_u16(lanes[3])),
+        ),
+    );
+
+    let mut out = [0; 26];
+    out.copy_from_slice(&ans[..26]);
+    out
+}
+
+/// decodes 26 characters, or returns `None` if any is invalid or the first
+/// is above `7`
+///
+/// # Safety
+///
+/// the CPU must support NEON
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn unmarshal_neon(s: &[u8; 26]) -> Option<Ulid> {
+    if s[0] > b'7' {
+        return None;
+    }
+
+    // valid characters are within 0x30 to 0x6F, and the lookup keeps the 0xFF
+    // for indices outside the table
+    let table = uint8x16x4_t(
+        vld1q_u8(DECODING[0x30..].as_ptr()),
+        vld1q_u8(DECODING[0x40..].as_ptr()),
+        vld1q_u8(DECODING[0x50..].as_ptr()),
+        vld1q_u8(DECODING[0x60..].as_ptr()),
+    );
+    let values =
+        |chars: uint8x16_t| vqtbx4q_u8(vdupq_n_u8(0xFF), table, vsubq_u8(chars, vdupq_n_u8(0x30)));
+
+    // the first 10 characters after 6 zeros, and the last 16
+    let first = values(vextq_u8(vdupq_n_u8(b'0'), vld1q_u8(s.as_ptr()), 10));
+    let second = values(vld1q_u8(s[10..].as_ptr()));
+
+    if vmaxvq_u8(vorrq_u8(first, second)) > 31 {
+        return None;
+    }
+
+    let evens = vuzp1q_u8(first, second);
+    let odds = vuzp2q_u8(first, second);
+    let pairs_low = vaddw_u8(vshll_n_u8(vget_low_u8(evens), 5), vget_low_u8(odds));
+    let pairs_high = vaddw_u8(vshll_n_u8(vget_high_u8(evens), 5), vget_high_u8(odds));
+
+    let evens = vuzp1q_u16(pairs_low, pairs_high);
+    let odds = vuzp2q_u16(pairs_low, pairs_high);
+    let quads_low = vaddw_u16(vshll_n_u16(vget_low_u16(evens), 10), vget_low_u16(odds));
+    let quads_high = vaddw_u16(vshll_n_u16(vget_high_u16(evens), 10), vget_high_u16(odds));
+
+    let evens = vuzp1q_u32(quads_low, quads_high);
+    let odds = vuzp2q_u32(quads_low, quads_high);
+    let merged = uint8x16x2_t(
+        vreinterpretq_u8_u64(vaddw_u32(
+            vshll_n_u32(vget_low_u32(evens), 20),
+            vget_low_u32(odds),
+        )),
+        vreinterpretq_u8_u64(vaddw_u32(
+            vshll_n_u32(vget_high_u32(evens), 20),
+            vget_high_u32(odds),
+        )),
+    );
+
+    let mut ans = [0; 16];
+    vst1q_u8(
+        ans.as_mut_ptr(),
+        vqtbl2q_u8(merged, vld1q_u8(GATHER.as_ptr())),
+    );
+    Some(Ulid(ans))
+}
@@ -0,0 +1,134 @@
+//! vectorized Crockford base32, with the instruction set picked at runtime
+//! where `std` can detect it, and at compile time otherwise
+//!
+//! For encoding, every output character holds 5 bits that span at most 2 input
+//! bytes. Each 16 bit lane gathers those 2 bytes with a byte shuffle, shifts the
+//! 5 bits to the top and then down to the bottom, and the resulting indices are
+//! mapped to characters with a 32 entry table lookup.
+//!
+//! For decoding, the 26 characters are loaded as two overlapping halves, and
+//! the first is shifted to start with 6 zeros, making 32 characters, which
+//! decode to 160 bits with the Ulid in the last 128. Characters are mapped to
+//! their values with table lookups, where invalid characters get the top bit
+//! set, and neighbouring values are then merged into 10, 20 and 40 bits wide
+//! lanes, whose bytes are shuffled into place.
+
+// without `std` the instruction set is fixed at compile time, which leaves the
+// others, or on other architectures all of this, unused
+#![cfg_attr(
+    any(
+        not(feature = "std"),
+        not(any(
+            target_arch = "x86",
+            target_arch = "x86_64",
+            all(target_arch = "aarch64", target_endian = "little")
+        ))
+    ),
+    allow(dead_code)
+)]
+
+use crate::Ulid;
+
+#[cfg(all(target_arch = "aarch64", target_endian = "little"))]
+pub(crate) mod aarch64;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub(crate) mod x86;
+
+/// byte indices forming the big endian 16 bit window for each of the 26
+/// characters, padded to 32 lanes. Out of range indices have the top bit set,
+/// which makes every supported shuffle produce a zero.
+static WINDOWS: [u8; 64] = windows();
+
+/// left shift moving the 5 bits of each character to the top of its window
+static SHIFTS: [u16; 32] = shifts();
+
+const fn windows() -> [u8; 64] {
+    let mut ans = [0xFF; 64];
+    let mut i = 0;
+
+    while i < 26 {
+        // the 128 bits are preceded by 2 zero bits, so character i starts at
+        // bit 5i - 2
+        let first = (5 * i as i32 - 2).div_euclid(8);
+
+        // lanes are little endian, so the second byte comes first
+        if first + 1 < 16 {
+            ans[2 * i] = (first + 1) as u8;
+        }
+        if first >= 0 {
+            ans[2 * i + 1] = first as u8;
+        }
+
+        i += 1;
+    }
+
+    ans
+}
+
+const fn shifts() -> [u16; 32] {
+    let mut ans = [0; 32];
+    let mut i = 0;
+
+    while i < 26 {
+        ans[i] = (5 * i as i32 - 2).rem_euclid(8) as u16;
+        i += 1;
+    }
+
+    ans
+}
+
+/// returns the result of the function for the best available vector
+/// instructions, or `None` if there are none
+macro_rules! dispatch {
+    ($avx2:ident, $ssse3:ident, $neon:ident, $arg:expr) => {{
+        #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "std"))]
+        {
+            if std::is_x86_feature_detected!("avx2") {
+                return Some(unsafe { x86::$avx2($arg) });
+            }
+
+            if std::is_x86_feature_detected!("ssse3") {
+                return Some(unsafe { x86::$ssse3($arg) });
+            }
+        }
+
+        #[cfg(all(
+            any(target_arch = "x86", target_arch = "x86_64"),
+            not(feature = "std"),
+            target_feature = "avx2"
+        ))]
+        return Some(unsafe { x86::$avx2($arg) });
+
+        #[cfg(all(
+            any(target_arch = "x86", target_arch = "x86_64"),
+            not(feature = "std"),
+            not(target_feature = "avx2"),
+            target_feature = "ssse3"
+        ))]
+        return Some(unsafe { x86::$ssse3($arg) });
+
+        #[cfg(all(
+            target_arch = "aarch64",
+            target_endian = "little",
+            target_feature = "neon"
+        ))]
+        return Some(unsafe { aarch64::$neon($arg) });
+
+        None
+    }};
+}
+
+/// encodes 16 bytes with the best available vector instructions, or returns
+/// `None` if there are none
+#[allow(unreachable_code, unused_variables)]
+pub(super) fn marshal(val: &[u8; 16]) -> Option<[u8; 26]> {
+    dispatch!(marshal_avx2, marshal_ssse3, marshal_neon, val)
+}
+
+/// decodes 26 characters with the best available vector instructions, or
+/// returns `None` if there are none, and `Some(None)` for invalid characters
+/// or a first character above `7`
+#[allow(unreachable_code, unused_variables)]
+pub(super) fn unmarshal(s: &[u8; 26]) -> Option<Option<Ulid>> {
+    dispatch!(unmarshal_avx2, unmarshal_ssse3, unmarshal_neon, s)
+}
@@ -0,0 +1,252 @@
+//! SSSE3 and AVX2
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+use super::{SHIFTS, WINDOWS};
+use crate::codec::{DECODING, ENCODING};
+use crate::Ulid;
+
+/// neither has a per lane 16 bit shift, so shift left by multiplying
+static MULTIPLIERS: [u16; 32] = multipliers();
+
+/// picks the last 16 of the 20 bytes in four 40 bit lanes, with the first two
+/// lanes in the first 16 bytes and the last two in the next
+static GATHER: [u8; 32] = [
+    0, 12, 11, 10, 9, 8, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, //
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 4, 3, 2, 1, 0, 12, 11, 10, 9, 8,
+];
+
+const fn multipliers() -> [u16; 32] {
+    let mut ans = [0; 32];
+    let mut i = 0;
+
+    while i < 32 {
+        ans[i] = 1 << SHIFTS[i];
+        i += 1;
+    }
+
+    ans
+}
+
+/// encodes 16 bytes into 26 characters
+///
+/// # Safety
+///
+/// the CPU must support SSSE3
+#[target_feature(enable = "ssse3")]
+pub(crate) unsafe fn marshal_ssse3(val: &[u8; 16]) -> [u8; 26] {
+    let input = _mm_loadu_si128(val.as_ptr() as *const __m128i);
+
+    let mut lanes = [_mm_setzero_si128(); 4];
+    for (i, lane) in lanes.iter_mut().enumerate() {
+        let window = _mm_shuffle_epi8(input, load(&WINDOWS[16 * i..]));
+        let multipliers = _mm_loadu_si128(MULTIPLIERS[8 * i..].as_ptr() as *const __m128i);
+
+        *lane = _mm_srli_epi16(_mm_mullo_epi16(window, multipliers), 11);
+    }
+
+    let mut ans = [0; 32];
+    _mm_storeu_si128(
+        ans.as_mut_ptr() as *mut __m128i,
+        lookup_ssse3(_mm_packus_epi16(lanes[0], lanes[1])),
+    );
+    _mm_storeu_si128(
+        ans[16..].as_mut_ptr() as *mut __m128i,
+        lookup_ssse3(_mm_packus_epi16(lanes[2], lanes[3])),
+    );
+
+    truncate(ans)
+}
+
+/// maps 16 indices below 32 to their characters
+#[target_feature(enable = "ssse3")]
+unsafe fn lookup_ssse3(indices: __m128i) -> __m128i {
+    // the shuffle only looks at the low 4 bits, so look up both halves of the
+    // alphabet and pick per lane
+    let in_second = _mm_cmpgt_epi8(indices, _mm_set1_epi8(15));
+
+    _mm_or_si128(
+        _mm_and_si128(in_second, _mm_shuffle_epi8(load(&ENCODING[16..]), indices)),
+        _mm_andnot_si128(in_second, _mm_shuffle_epi8(load(ENCODING), indices)),
+    )
+}
+
+/// encodes 16 bytes into 26 characters
+///
+/// # Safety
+///
+/// the CPU must support AVX2
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn marshal_avx2(val: &[u8; 16]) -> [u8; 26] {
+    // shuffles stay within 128 bit halves, so both halves get the input
+    let input = _mm256_broadcastsi128_si256(load(val));
+
+    let mut lanes = [_mm256_setzero_si256(); 2];
+    for (i, lane) in lanes.iter_mut().enumerate() {
+        let window = _mm256_shuffle_epi8(
+            input,
+            _mm256_loadu_si256(WINDOWS[32 * i..].as_ptr() as *const __m256i),
+        );
+        let multipliers = _mm256_loadu_si256(MULTIPLIERS[16 * i..].as_ptr() as *const __m256i);
+
+        *lane = _mm256_srli_epi16(_mm256_mullo_epi16(window, multipliers), 11);
+    }
+
+    // packing interleaves the 64 bit quarters of the two inputs
+    let indices = _mm256_permute4x64_epi64(_mm256_packus_epi16(lanes[0], lanes[1]), 0b11_01_10_00);
+
+    let chars = _mm256_blendv_epi8(
+        _mm256_shuffle_epi8(_mm256_broadcastsi128_si256(load(ENCODING)), indices),
+        _mm256_shuffle_epi8(_mm256_broadcastsi128_si256(load(&ENCODING[16..])), indices),
+        _mm256_cmpgt_epi8(indices, _mm256_set1_epi8(15)),
+    );
+
+    let mut ans = [0; 32];
+    _mm256_storeu_si256(ans.as_mut_ptr() as *mut __m256i, chars);
+
+    truncate(ans)
+}
+
+/// decodes 26 characters, or returns `None` if any is invalid or the first
+/// is above `7`
+///
+/// # Safety
+///
+/// the CPU must support SSSE3
+#[target_feature(enable = "ssse3")]
+pub(crate) unsafe fn unmarshal_ssse3(s: &[u8; 26]) -> Option<Ulid> {
+    if s[0] > b'7' {
+        return None;
+    }
+
+    let first = values_ssse3(first_half(s));
+    let second = values_ssse3(load(&s[10..]));
+
+    if _mm_movemask_epi8(_mm_or_si128(first, second)) != 0 {
+        return None;
+    }
+
+    let val = _mm_or_si128(
+        _mm_shuffle_epi8(merge_ssse3(first), load(&GATHER)),
+        _mm_shuffle_epi8(merge_ssse3(second), load(&GATHER[16..])),
+    );
+
+    let mut ans = [0; 16];
+    _mm_storeu_si128(ans.as_mut_ptr() as *mut __m128i, val);
+    Some(Ulid(ans))
+}
+
+/// maps 16 characters to their values, or 0xFF if invalid
+#[target_feature(enable = "ssse3")]
+unsafe fn values_ssse3(chars: __m128i) -> __m128i {
+    let low = _mm_and_si128(chars, _mm_set1_epi8(0x0F));
+    let high = _mm_and_si128(_mm_srli_epi16(chars, 4), _mm_set1_epi8(0x0F));
+
+    // valid characters are within 0x30 to 0x5F, so look up the low nibble in
+    // the 16 entries for each of the 3 high nibbles
+    let mut ans = _mm_set1_epi8(-1);
+    for high_nibble in 3..6 {
+        let matches = _mm_cmpeq_epi8(high, _mm_set1_epi8(high_nibble as i8));
+        let values = _mm_shuffle_epi8(load(&DECODING[16 * high_nibble..]), low);
+
+        ans = _mm_or_si128(
+            _mm_and_si128(matches, values),
+            _mm_andnot_si128(matches, ans),
+        );
+    }
+
+    ans
+}
+
+/// merges 16 values of 5 bits into two lanes of 40 bits
+#[target_feature(enable = "ssse3")]
+unsafe fn merge_ssse3(values: __m128i) -> __m128i {
+    let pairs = _mm_maddubs_epi16(values, _mm_set1_epi16(0x0120));
+    let quads = _mm_madd_epi16(pairs, _mm_set1_epi32(0x0001_0400));
+
+    _mm_or_si128(
+        _mm_slli_epi64(_mm_and_si128(quads, _mm_set_epi32(0, -1, 0, -1)), 20),
+        _mm_srli_epi64(quads, 32),
+    )
+}
+
+/// decodes 26 characters, or returns `None` if any is invalid or the first
+/// is above `7`
+///
+/// # Safety
+///
+/// the CPU must support AVX2
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn unmarshal_avx2(s: &[u8; 26]) -> Option<Ulid> {
+    if s[0] > b'7' {
+        return None;
+    }
+
+    let chars = _mm256_inserti128_si256(_mm256_castsi128_si256(first_half(s)), load(&s[10..]), 1);
+
+    let low = _mm256_and_si256(chars, _mm256_set1_epi8(0x0F));
+    let high = _mm256_and_si256(_mm256_srli_epi16(chars, 4), _mm256_set1_epi8(0x0F));
+
+    let mut values = _mm256_set1_epi8(-1);
+    for high_nibble in 3..6 {
+        let table = _mm256_broadcastsi128_si256(load(&DECODING[16 * high_nibble..]));
+
+        values = _mm256_blendv_epi8(
+            values,
+            _mm256_shuffle_epi8(table, low),
+            _mm256_cmpeq_epi8(high, _mm256_set1_epi8(high_nibble as i8)),
+        );
+    }
+
+    if _mm256_movemask_epi8(values) != 0 {
+        return None;
+    }
+
+    let pairs = _mm256_maddubs_epi16(values, _mm256_set1_epi16(0x0120));
+    let quads = _mm256_madd_epi16(pairs, _mm256_set1_epi32(0x0001_0400));
+    let merged = _mm256_or_si256(
+        _mm256_slli_epi64(
+            _mm256_and_si256(quads, _mm256_set_epi32(0, -1, 0, -1, 0, -1, 0, -1)),
+            20,
+        ),
+        _mm256_srli_epi64(quads, 32),
+    );
+
+    let bytes = _mm256_shuffle_epi8(
+        merged,
+        _mm256_loadu_si256(GATHER.as_ptr() as *const __m256i),
+    );
+    let val = _mm_or_si128(
+        _mm256_castsi256_si128(bytes),
+        _mm256_extracti128_si256(bytes, 1),
+    );
+
+    let mut ans = [0; 16];
+    _mm_storeu_si128(ans.as_mut_ptr() as *mut __m128i, val);
+    Some(Ulid(ans))
+}
+
+/// loads the first 10 characters after 6 zeros
+#[inline(always)]
+unsafe fn first_half(s: &[u8; 26]) -> __m128i {
+    _mm_or_si128(
+        _mm_slli_si128(load(s), 6),
+        _mm_set_epi8(0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 48, 48, 48, 48, 48, 48),
+    )
+}
+
+/// loads the first 16 bytes of `s`, which must be at least that long
+#[inline(always)]
+unsafe fn load(s: &[u8]) -> __m128i {
+    debug_assert!(s.len() >= 16);
+    _mm_loadu_si128(s.as_ptr() as *const __m128i)
+}
+
+fn truncate(ans: [u8; 32]) -> [u8; 26] {
+    let mut out = [0; 26];
+    out.copy_from_slice(&ans[..26]);
+    out
+}
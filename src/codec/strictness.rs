@@ -0,0 +1,56 @@
+//! parsing with a choice between spec-strict validation and permissive
+//! ingestion
+
+use core::convert::TryFrom;
+
+use crate::{Ulid, UlidError};
+
+/// how strictly `Ulid::unmarshal_with` validates its input
+///
+/// All of them reject a first character above `7`, which would overflow the
+/// 128 bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Strictness {
+    /// only the uppercase form produced by `marshal`
+    CanonicalOnly,
+
+    /// the ULID spec, Crockford's base32 in either case
+    Strict,
+
+    /// also maps the look-alikes `I` and `L` to `1` and `O` to `0`, as
+    /// Crockford's decoding does
+    Lenient,
+}
+
+impl Ulid {
+    /// unmarshals a string-like into a ULID, validating it as `strictness`
+    /// says
+    ///
+    /// Errors carry the byte as found in the input.
+    pub fn unmarshal_with<S>(s: S, strictness: Strictness) -> Result<Ulid, UlidError>
+    where
+        S: AsRef<[u8]>,
+    {
+        let s = s.as_ref();
+        let mut chars =
+            <[u8; 26]>::try_from(s).map_err(|_| UlidError::InvalidLength { len: s.len() })?;
+
+        if strictness != Strictness::CanonicalOnly {
+            for c in chars.iter_mut() {
+                *c = match (strictness, c.to_ascii_uppercase()) {
+                    (Strictness::Lenient, b'I') | (Strictness::Lenient, b'L') => b'1',
+                    (Strictness::Lenient, b'O') => b'0',
+                    (_, upper) => upper,
+                };
+            }
+        }
+
+        Ulid::unmarshal(chars).map_err(|e| match e {
+            UlidError::InvalidCharacter { index, .. } => UlidError::InvalidCharacter {
+                index,
+                byte: s[index],
+            },
+            e => e,
+        })
+    }
+}
@@ -0,0 +1,523 @@
+//! support for `#[derive(UlidId)]`, which calls `__ulid_id_impls!` to forward
+//! the integrations enabled on this crate to the newtype
+//!
+//! Each integration has a macro that expands to nothing when its feature is
+//! disabled, since the derive cannot see the features of ulid-rs. The crates
+//! are re-exported here so the expansion does not need them as dependencies.
+
+#[cfg(feature = "borsh")]
+pub use borsh;
+#[cfg(feature = "diesel")]
+pub use diesel;
+#[cfg(feature = "postgres")]
+pub use postgres_types;
+#[cfg(feature = "redis")]
+pub use redis;
+#[cfg(feature = "rusqlite")]
+pub use rusqlite;
+#[cfg(feature = "scylla")]
+pub use scylla;
+#[cfg(feature = "sea-orm")]
+pub use sea_orm;
+#[cfg(feature = "serde")]
+pub use serde;
+#[cfg(feature = "sqlx")]
+pub use sqlx;
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ulid_id_impls {
+    ($t:ty) => {
+        $crate::__ulid_id_serde!($t);
+        $crate::__ulid_id_borsh!($t);
+        $crate::__ulid_id_diesel!($t);
+        $crate::__ulid_id_postgres!($t);
+        $crate::__ulid_id_redis!($t);
+        $crate::__ulid_id_rusqlite!($t);
+        $crate::__ulid_id_scylla!($t);
+        $crate::__ulid_id_sea_orm!($t);
+        $crate::__ulid_id_sqlx!($t);
+    };
+}
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ulid_id_serde {
+    ($t:ty) => {
+        impl $crate::derive::serde::Serialize for $t {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::derive::serde::Serializer,
+            {
+                ::core::convert::AsRef::<$crate::Ulid>::as_ref(self).serialize(serializer)
+            }
+        }
+
+        impl<'de> $crate::derive::serde::Deserialize<'de> for $t {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: $crate::derive::serde::Deserializer<'de>,
+            {
+                <$crate::Ulid as $crate::derive::serde::Deserialize>::deserialize(deserializer)
+                    .map(<$t>::from)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ulid_id_serde {
+    ($t:ty) => {};
+}
+
+#[cfg(feature = "borsh")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ulid_id_borsh {
+    ($t:ty) => {
+        impl $crate::derive::borsh::BorshSerialize for $t {
+            fn serialize<W: $crate::derive::borsh::io::Write>(
+                &self,
+                writer: &mut W,
+            ) -> $crate::derive::borsh::io::Result<()> {
+                $crate::derive::borsh::BorshSerialize::serialize(
+                    ::core::convert::AsRef::<$crate::Ulid>::as_ref(self),
+                    writer,
+                )
+            }
+        }
+
+        impl $crate::derive::borsh::BorshDeserialize for $t {
+            fn deserialize_reader<R: $crate::derive::borsh::io::Read>(
+                reader: &mut R,
+            ) -> $crate::derive::borsh::io::Result<Self> {
+                <$crate::Ulid as $crate::derive::borsh::BorshDeserialize>::deserialize_reader(
+                    reader,
+                )
+                .map(<$t>::from)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "borsh"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ulid_id_borsh {
+    ($t:ty) => {};
+}
+
+/// only `ToSql` and `FromSql`, as `AsExpression` and `FromSqlRow` are derived
+/// with diesel's own derives
+#[cfg(feature = "diesel")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ulid_id_diesel {
+    ($t:ty) => {
+        impl<DB>
+            $crate::derive::diesel::serialize::ToSql<$crate::derive::diesel::sql_types::Binary, DB>
+            for $t
+        where
+            DB: $crate::derive::diesel::backend::Backend,
+            $crate::Ulid: $crate::derive::diesel::serialize::ToSql<
+                $crate::derive::diesel::sql_types::Binary,
+                DB,
+            >,
+        {
+            fn to_sql<'b>(
+                &'b self,
+                out: &mut $crate::derive::diesel::serialize::Output<'b, '_, DB>,
+            ) -> $crate::derive::diesel::serialize::Result {
+                $crate::derive::diesel::serialize::ToSql::<
+                    $crate::derive::diesel::sql_types::Binary,
+                    DB,
+                >::to_sql(::core::convert::AsRef::<$crate::Ulid>::as_ref(self), out)
+            }
+        }
+
+        impl<DB>
+            $crate::derive::diesel::deserialize::FromSql<
+                $crate::derive::diesel::sql_types::Binary,
+                DB,
+            > for $t
+        where
+            DB: $crate::derive::diesel::backend::Backend,
+            $crate::Ulid: $crate::derive::diesel::deserialize::FromSql<
+                $crate::derive::diesel::sql_types::Binary,
+                DB,
+            >,
+        {
+            fn from_sql(
+                bytes: DB::RawValue<'_>,
+            ) -> $crate::derive::diesel::deserialize::Result<Self> {
+                <$crate::Ulid as $crate::derive::diesel::deserialize::FromSql<
+                    $crate::derive::diesel::sql_types::Binary,
+                    DB,
+                >>::from_sql(bytes)
+                .map(<$t>::from)
+            }
+        }
+
+        impl
+            $crate::derive::diesel::serialize::ToSql<
+                $crate::derive::diesel::sql_types::Uuid,
+                $crate::derive::diesel::pg::Pg,
+            > for $t
+        {
+            fn to_sql<'b>(
+                &'b self,
+                out: &mut $crate::derive::diesel::serialize::Output<
+                    'b,
+                    '_,
+                    $crate::derive::diesel::pg::Pg,
+                >,
+            ) -> $crate::derive::diesel::serialize::Result {
+                $crate::derive::diesel::serialize::ToSql::<
+                    $crate::derive::diesel::sql_types::Uuid,
+                    $crate::derive::diesel::pg::Pg,
+                >::to_sql(::core::convert::AsRef::<$crate::Ulid>::as_ref(self), out)
+            }
+        }
+
+        impl
+            $crate::derive::diesel::deserialize::FromSql<
+                $crate::derive::diesel::sql_types::Uuid,
+                $crate::derive::diesel::pg::Pg,
+            > for $t
+        {
+            fn from_sql(
+                value: $crate::derive::diesel::pg::PgValue<'_>,
+            ) -> $crate::derive::diesel::deserialize::Result<Self> {
+                <$crate::Ulid as $crate::derive::diesel::deserialize::FromSql<
+                    $crate::derive::diesel::sql_types::Uuid,
+                    $crate::derive::diesel::pg::Pg,
+                >>::from_sql(value)
+                .map(<$t>::from)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "diesel"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ulid_id_diesel {
+    ($t:ty) => {};
+}
+
+#[cfg(feature = "postgres")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ulid_id_postgres {
+    ($t:ty) => {
+        impl<'a> $crate::derive::postgres_types::FromSql<'a> for $t {
+            fn from_sql(
+                ty: &$crate::derive::postgres_types::Type,
+                raw: &'a [u8],
+            ) -> ::core::result::Result<
+                Self,
+                ::std::boxed::Box<dyn ::std::error::Error + Sync + Send>,
+            > {
+                <$crate::Ulid as $crate::derive::postgres_types::FromSql>::from_sql(ty, raw)
+                    .map(<$t>::from)
+            }
+
+            fn accepts(ty: &$crate::derive::postgres_types::Type) -> bool {
+                <$crate::Ulid as $crate::derive::postgres_types::FromSql>::accepts(ty)
+            }
+        }
+
+        impl $crate::derive::postgres_types::ToSql for $t {
+            fn to_sql(
+                &self,
+                ty: &$crate::derive::postgres_types::Type,
+                out: &mut $crate::derive::postgres_types::private::BytesMut,
+            ) -> ::core::result::Result<
+                $crate::derive::postgres_types::IsNull,
+                ::std::boxed::Box<dyn ::std::error::Error + Sync + Send>,
+            > {
+                $crate::derive::postgres_types::ToSql::to_sql(
+                    ::core::convert::AsRef::<$crate::Ulid>::as_ref(self),
+                    ty,
+                    out,
+                )
+            }
+
+            fn accepts(ty: &$crate::derive::postgres_types::Type) -> bool {
+                <$crate::Ulid as $crate::derive::postgres_types::ToSql>::accepts(ty)
+            }
+
+            $crate::derive::postgres_types::to_sql_checked!();
+        }
+    };
+}
+
+#[cfg(not(feature = "postgres"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ulid_id_postgres {
+    ($t:ty) => {};
+}
+
+#[cfg(feature = "redis")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ulid_id_redis {
+    ($t:ty) => {
+        impl $crate::derive::redis::ToRedisArgs for $t {
+            fn write_redis_args<W>(&self, out: &mut W)
+            where
+                W: ?Sized + $crate::derive::redis::RedisWrite,
+            {
+                $crate::derive::redis::ToRedisArgs::write_redis_args(
+                    ::core::convert::AsRef::<$crate::Ulid>::as_ref(self),
+                    out,
+                )
+            }
+        }
+
+        impl $crate::derive::redis::FromRedisValue for $t {
+            fn from_redis_value(
+                v: &$crate::derive::redis::Value,
+            ) -> $crate::derive::redis::RedisResult<Self> {
+                <$crate::Ulid as $crate::derive::redis::FromRedisValue>::from_redis_value(v)
+                    .map(<$t>::from)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "redis"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ulid_id_redis {
+    ($t:ty) => {};
+}
+
+#[cfg(feature = "rusqlite")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ulid_id_rusqlite {
+    ($t:ty) => {
+        impl $crate::derive::rusqlite::types::ToSql for $t {
+            fn to_sql(
+                &self,
+            ) -> $crate::derive::rusqlite::Result<$crate::derive::rusqlite::types::ToSqlOutput<'_>>
+            {
+                $crate::derive::rusqlite::types::ToSql::to_sql(
+                    ::core::convert::AsRef::<$crate::Ulid>::as_ref(self),
+                )
+            }
+        }
+
+        impl $crate::derive::rusqlite::types::FromSql for $t {
+            fn column_result(
+                value: $crate::derive::rusqlite::types::ValueRef<'_>,
+            ) -> $crate::derive::rusqlite::types::FromSqlResult<Self> {
+                <$crate::Ulid as $crate::derive::rusqlite::types::FromSql>::column_result(value)
+                    .map(<$t>::from)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "rusqlite"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ulid_id_rusqlite {
+    ($t:ty) => {};
+}
+
+#[cfg(feature = "scylla")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ulid_id_scylla {
+    ($t:ty) => {
+        impl $crate::derive::scylla::serialize::value::SerializeValue for $t {
+            fn serialize<'b>(
+                &self,
+                typ: &$crate::derive::scylla::cluster::metadata::ColumnType,
+                writer: $crate::derive::scylla::serialize::writers::CellWriter<'b>,
+            ) -> ::core::result::Result<
+                $crate::derive::scylla::serialize::writers::WrittenCellProof<'b>,
+                $crate::derive::scylla::serialize::SerializationError,
+            > {
+                $crate::derive::scylla::serialize::value::SerializeValue::serialize(
+                    ::core::convert::AsRef::<$crate::Ulid>::as_ref(self),
+                    typ,
+                    writer,
+                )
+            }
+        }
+
+        impl<'frame, 'metadata> $crate::derive::scylla::deserialize::value::DeserializeValue<'frame, 'metadata>
+            for $t
+        {
+            fn type_check(
+                typ: &$crate::derive::scylla::cluster::metadata::ColumnType,
+            ) -> ::core::result::Result<(), $crate::derive::scylla::deserialize::TypeCheckError>
+            {
+                <$crate::Ulid as $crate::derive::scylla::deserialize::value::DeserializeValue>::type_check(typ)
+            }
+
+            fn deserialize(
+                typ: &'metadata $crate::derive::scylla::cluster::metadata::ColumnType<'metadata>,
+                v: ::core::option::Option<$crate::derive::scylla::deserialize::FrameSlice<'frame>>,
+            ) -> ::core::result::Result<Self, $crate::derive::scylla::deserialize::DeserializationError>
+            {
+                <$crate::Ulid as $crate::derive::scylla::deserialize::value::DeserializeValue>::deserialize(typ, v)
+                    .map(<$t>::from)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "scylla"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ulid_id_scylla {
+    ($t:ty) => {};
+}
+
+#[cfg(feature = "sea-orm")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ulid_id_sea_orm {
+    ($t:ty) => {
+        impl ::core::convert::From<$t> for $crate::derive::sea_orm::sea_query::Value {
+            fn from(id: $t) -> Self {
+                $crate::Ulid::from(id).into()
+            }
+        }
+
+        impl $crate::derive::sea_orm::sea_query::Nullable for $t {
+            fn null() -> $crate::derive::sea_orm::sea_query::Value {
+                <$crate::Ulid as $crate::derive::sea_orm::sea_query::Nullable>::null()
+            }
+        }
+
+        impl $crate::derive::sea_orm::sea_query::ValueType for $t {
+            fn try_from(
+                v: $crate::derive::sea_orm::sea_query::Value,
+            ) -> ::core::result::Result<Self, $crate::derive::sea_orm::sea_query::ValueTypeErr>
+            {
+                <$crate::Ulid as $crate::derive::sea_orm::sea_query::ValueType>::try_from(v)
+                    .map(<$t>::from)
+            }
+
+            fn type_name() -> ::std::string::String {
+                ::std::string::ToString::to_string(::core::stringify!($t))
+            }
+
+            fn array_type() -> $crate::derive::sea_orm::sea_query::ArrayType {
+                <$crate::Ulid as $crate::derive::sea_orm::sea_query::ValueType>::array_type()
+            }
+
+            fn column_type() -> $crate::derive::sea_orm::sea_query::ColumnType {
+                <$crate::Ulid as $crate::derive::sea_orm::sea_query::ValueType>::column_type()
+            }
+        }
+
+        impl $crate::derive::sea_orm::TryGetable for $t {
+            fn try_get_by<I: $crate::derive::sea_orm::ColIdx>(
+                res: &$crate::derive::sea_orm::QueryResult,
+                index: I,
+            ) -> ::core::result::Result<Self, $crate::derive::sea_orm::TryGetError> {
+                <$crate::Ulid as $crate::derive::sea_orm::TryGetable>::try_get_by(res, index)
+                    .map(<$t>::from)
+            }
+        }
+
+        impl $crate::derive::sea_orm::TryFromU64 for $t {
+            fn try_from_u64(
+                _: u64,
+            ) -> ::core::result::Result<Self, $crate::derive::sea_orm::DbErr> {
+                ::core::result::Result::Err($crate::derive::sea_orm::DbErr::ConvertFromU64(
+                    ::core::stringify!($t),
+                ))
+            }
+        }
+
+        impl $crate::derive::sea_orm::IntoActiveValue<$t> for $t {
+            fn into_active_value(self) -> $crate::derive::sea_orm::ActiveValue<$t> {
+                $crate::derive::sea_orm::ActiveValue::Set(self)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "sea-orm"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ulid_id_sea_orm {
+    ($t:ty) => {};
+}
+
+#[cfg(feature = "sqlx")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ulid_id_sqlx {
+    ($t:ty) => {
+        impl<DB> $crate::derive::sqlx::Type<DB> for $t
+        where
+            DB: $crate::derive::sqlx::Database,
+            $crate::Ulid: $crate::derive::sqlx::Type<DB>,
+        {
+            fn type_info() -> DB::TypeInfo {
+                <$crate::Ulid as $crate::derive::sqlx::Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &DB::TypeInfo) -> bool {
+                <$crate::Ulid as $crate::derive::sqlx::Type<DB>>::compatible(ty)
+            }
+        }
+
+        impl $crate::derive::sqlx::postgres::PgHasArrayType for $t {
+            fn array_type_info() -> $crate::derive::sqlx::postgres::PgTypeInfo {
+                <$crate::Ulid as $crate::derive::sqlx::postgres::PgHasArrayType>::array_type_info()
+            }
+        }
+
+        impl<'q, DB> $crate::derive::sqlx::Encode<'q, DB> for $t
+        where
+            DB: $crate::derive::sqlx::Database,
+            $crate::Ulid: $crate::derive::sqlx::Encode<'q, DB>,
+        {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <DB as $crate::derive::sqlx::Database>::ArgumentBuffer<'q>,
+            ) -> ::core::result::Result<
+                $crate::derive::sqlx::encode::IsNull,
+                $crate::derive::sqlx::error::BoxDynError,
+            > {
+                $crate::derive::sqlx::Encode::<'q, DB>::encode_by_ref(
+                    ::core::convert::AsRef::<$crate::Ulid>::as_ref(self),
+                    buf,
+                )
+            }
+        }
+
+        impl<'r, DB> $crate::derive::sqlx::Decode<'r, DB> for $t
+        where
+            DB: $crate::derive::sqlx::Database,
+            $crate::Ulid: $crate::derive::sqlx::Decode<'r, DB>,
+        {
+            fn decode(
+                value: <DB as $crate::derive::sqlx::Database>::ValueRef<'r>,
+            ) -> ::core::result::Result<Self, $crate::derive::sqlx::error::BoxDynError> {
+                <$crate::Ulid as $crate::derive::sqlx::Decode<'r, DB>>::decode(value)
+                    .map(<$t>::from)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "sqlx"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ulid_id_sqlx {
+    ($t:ty) => {};
+}
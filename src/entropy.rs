@@ -0,0 +1,198 @@
+//! the random part of a Ulid, for the constructors that take a source of
+//! entropy
+
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::fmt;
+
+/// a source for the 10 bytes of entropy in a Ulid
+///
+/// Implemented by closures returning a `u8`, which are called once per byte,
+/// by `RngEntropy` with the `rand_core` feature and by `HalRngEntropy` with the
+/// `embedded-hal` feature.
+pub trait EntropySource {
+    /// fills `entropy` with random bytes
+    fn fill(&mut self, entropy: &mut [u8; 10]);
+}
+
+impl<F> EntropySource for F
+where
+    F: FnMut() -> u8,
+{
+    fn fill(&mut self, entropy: &mut [u8; 10]) {
+        for byte in entropy.iter_mut() {
+            *byte = self();
+        }
+    }
+}
+
+/// an `EntropySource` reading from a `rand_core::RngCore`, in a single
+/// `fill_bytes` call
+///
+/// Takes `&mut R` as well, to keep using the rng afterwards.
+#[cfg(feature = "rand_core")]
+#[derive(Clone, Debug, Default)]
+pub struct RngEntropy<R>(pub R);
+
+#[cfg(feature = "rand_core")]
+impl<R> EntropySource for RngEntropy<R>
+where
+    R: rand_core::RngCore,
+{
+    fn fill(&mut self, entropy: &mut [u8; 10]) {
+        self.0.fill_bytes(entropy);
+    }
+}
+
+/// an `EntropySource` reading from a borrowed hardware RNG peripheral through
+/// `embedded_hal::blocking::rng::Read`, in a single `read` call
+///
+/// Peripherals implementing `rand_core::RngCore` instead work with
+/// `RngEntropy`.
+///
+/// # Panics
+///
+/// if the peripheral fails to provide entropy
+#[cfg(feature = "embedded-hal")]
+#[derive(Debug)]
+pub struct HalRngEntropy<'a, R>(pub &'a mut R);
+
+#[cfg(feature = "embedded-hal")]
+impl<R> EntropySource for HalRngEntropy<'_, R>
+where
+    R: embedded_hal::blocking::rng::Read,
+{
+    fn fill(&mut self, entropy: &mut [u8; 10]) {
+        if self.0.read(entropy).is_err() {
+            panic!("hardware rng failed to provide entropy");
+        }
+    }
+}
+
+/// an `EntropySource` reading from the operating system in large chunks, 4 KiB
+/// by default, and handing out 10 bytes at a time
+///
+/// Saves a system call per Ulid, for creating many of them. Pass it as
+/// `&mut BufferedEntropy` to keep the rest of the buffer. `from_fn` buffers any
+/// other source that fills a slice at a time.
+///
+/// A clone starts with an empty buffer, so it never hands out the same bytes
+/// as the original.
+///
+/// # Panics
+///
+/// if the operating system fails to provide entropy
+#[cfg(feature = "alloc")]
+pub struct BufferedEntropy<F = fn(&mut [u8])> {
+    buf: Vec<u8>,
+    used: usize,
+    fill: F,
+}
+
+#[cfg(all(feature = "std", feature = "getrandom"))]
+impl BufferedEntropy {
+    /// creates new BufferedEntropy reading 4 KiB at a time
+    pub fn new() -> BufferedEntropy {
+        BufferedEntropy::with_capacity(4096)
+    }
+
+    /// creates new BufferedEntropy reading `bytes` at a time, rounded down to
+    /// whole Ulids
+    ///
+    /// # Panics
+    ///
+    /// if `bytes` is less than 10
+    pub fn with_capacity(bytes: usize) -> BufferedEntropy {
+        BufferedEntropy::from_fn(bytes, crate::generator::fill_os_entropy)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F> BufferedEntropy<F>
+where
+    F: FnMut(&mut [u8]),
+{
+    /// creates new BufferedEntropy calling `fill` for `bytes` at a time,
+    /// rounded down to whole Ulids
+    ///
+    /// # Panics
+    ///
+    /// if `bytes` is less than 10
+    pub fn from_fn(bytes: usize, fill: F) -> BufferedEntropy<F> {
+        assert!(bytes >= 10, "buffer smaller than one Ulid");
+
+        let len = bytes - bytes % 10;
+        BufferedEntropy {
+            buf: vec![0; len],
+            used: len,
+            fill,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F> BufferedEntropy<F> {
+    /// returns how many bytes are read at a time
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+}
+
+#[cfg(all(feature = "std", feature = "getrandom"))]
+impl Default for BufferedEntropy {
+    fn default() -> Self {
+        BufferedEntropy::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F> Clone for BufferedEntropy<F>
+where
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        // copying the unread bytes would hand them out twice
+        BufferedEntropy {
+            buf: vec![0; self.buf.len()],
+            used: self.buf.len(),
+            fill: self.fill.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F> fmt::Debug for BufferedEntropy<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // leaves out the unread entropy
+        f.debug_struct("BufferedEntropy")
+            .field("capacity", &self.buf.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F> EntropySource for BufferedEntropy<F>
+where
+    F: FnMut(&mut [u8]),
+{
+    fn fill(&mut self, entropy: &mut [u8; 10]) {
+        if self.used == self.buf.len() {
+            (self.fill)(&mut self.buf);
+            self.used = 0;
+        }
+
+        entropy.copy_from_slice(&self.buf[self.used..self.used + 10]);
+        self.used += 10;
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F> EntropySource for &mut BufferedEntropy<F>
+where
+    F: FnMut(&mut [u8]),
+{
+    fn fill(&mut self, entropy: &mut [u8; 10]) {
+        (**self).fill(entropy)
+    }
+}
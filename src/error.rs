@@ -0,0 +1,124 @@
+//! the error type for parsing and generating Ulids
+
+use core::fmt;
+
+/// errors
+///
+/// Non-exhaustive, so that new failure modes can be added without breaking
+/// `match` statements downstream.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Error), uniffi(flat_error))]
+pub enum UlidError {
+    /// parsing error, with the length of the input
+    InvalidLength {
+        /// length of the input, in bytes
+        len: usize,
+    },
+
+    /// parsing error, with the first invalid byte and its position
+    InvalidCharacter {
+        /// position of the byte in the input
+        index: usize,
+        /// the byte found
+        byte: u8,
+    },
+
+    /// parsing error, for a check symbol that does not match the Ulid
+    InvalidCheckSymbol,
+
+    /// verification error, for a signed Ulid whose tag does not match
+    InvalidSignature,
+
+    /// binary decoding error, for a buffer that is not 16 bytes
+    InvalidBufferLength {
+        /// length of the buffer, in bytes
+        len: usize,
+    },
+
+    /// hex parsing error
+    InvalidHexLength,
+
+    /// hex parsing error
+    InvalidHexCharacter,
+
+    /// base64 parsing error
+    InvalidBase64Length,
+
+    /// base64 parsing error
+    InvalidBase64Character,
+
+    /// base58 parsing error
+    InvalidBase58Length,
+
+    /// base58 parsing error
+    InvalidBase58Character,
+
+    /// conversion error
+    InvalidUuidVersion,
+
+    /// conversion error
+    InvalidKsuidTimestamp,
+
+    /// conversion error
+    InvalidObjectIdTimestamp,
+
+    /// generation error, for a timestamp that does not fit in 48 bits
+    InvalidTimestamp,
+
+    /// generation error
+    Overflow,
+
+    /// generation error, for a `ShardedGenerator` with an owner on every lane
+    NoFreeLane,
+}
+
+impl fmt::Display for UlidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            UlidError::InvalidLength { len } => {
+                return write!(f, "invalid length {} for unmarshal", len);
+            }
+            UlidError::InvalidCharacter { index, byte } if byte.is_ascii_graphic() => {
+                return write!(
+                    f,
+                    "invalid character '{}' at index {} encountered while parsing",
+                    *byte as char, index
+                );
+            }
+            UlidError::InvalidCharacter { index, byte } => {
+                return write!(
+                    f,
+                    "invalid byte 0x{:02X} at index {} encountered while parsing",
+                    byte, index
+                );
+            }
+            UlidError::InvalidCheckSymbol => "check symbol does not match, the ulid was mistyped",
+            UlidError::InvalidSignature => "signature does not match the ulid",
+            UlidError::InvalidBufferLength { len } => {
+                return write!(f, "invalid buffer length {}, expected 16 bytes", len);
+            }
+            UlidError::InvalidHexLength => "invalid length for hex decode, expected 32 digits",
+            UlidError::InvalidHexCharacter => "invalid hex digit encountered while parsing",
+            UlidError::InvalidBase64Length => {
+                "invalid length for base64url decode, expected 22 characters"
+            }
+            UlidError::InvalidBase64Character => {
+                "invalid base64url character encountered while parsing"
+            }
+            UlidError::InvalidBase58Length => "base58 input does not decode to 16 bytes",
+            UlidError::InvalidBase58Character => {
+                "invalid base58 character encountered while parsing"
+            }
+            UlidError::InvalidUuidVersion => "uuid is not an RFC 4122 version 7 uuid",
+            UlidError::InvalidKsuidTimestamp => "timestamp is outside the range of a ksuid",
+            UlidError::InvalidObjectIdTimestamp => "timestamp is outside the range of an objectid",
+            UlidError::InvalidTimestamp => "timestamp does not fit in 48 bits",
+            UlidError::Overflow => "entropy overflowed for the same millisecond",
+            UlidError::NoFreeLane => "every lane of the sharded generator has an owner",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UlidError {}
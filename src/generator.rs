@@ -0,0 +1,1192 @@
+//! Ulid creation from a timestamp and a source of entropy
+//!
+//! With `getrandom`, entropy from the operating system is read 4 KiB at a time
+//! into a buffer local to each thread, and refilled in a forked child. That
+//! cuts the time of `Ulid::generate` to about a fifth, as the `generate` and
+//! `generate_unbuffered` benches show with `--features getrandom`.
+
+use crate::{Clock, EntropySource, SystemClock, Ulid, UlidError, ENTROPY_MASK};
+
+impl Ulid {
+    /// creates new Ulid from a timestamp and a source of entropy
+    pub fn new<E>(timestamp: u64, entropy: E) -> Ulid
+    where
+        E: EntropySource,
+    {
+        let mut ans = Ulid([0; 16]);
+        ans.encode_time(timestamp);
+        ans.encode_entropy(entropy);
+        ans
+    }
+
+    /// creates new Ulid from the current time in milliseconds and a source of entropy
+    ///
+    /// On `wasm32-unknown-unknown` this needs the `wasm` feature, which reads
+    /// the time from JavaScript's `Date.now`.
+    #[cfg(feature = "std")]
+    pub fn now<E>(entropy: E) -> Ulid
+    where
+        E: EntropySource,
+    {
+        Ulid::new(crate::clock::now_millis(), entropy)
+    }
+
+    /// creates new Ulid from the time of a custom clock and a source of entropy
+    pub fn now_with_clock<C, E>(clock: &C, entropy: E) -> Ulid
+    where
+        C: Clock,
+        E: EntropySource,
+    {
+        Ulid::new(clock.now_millis(), entropy)
+    }
+
+    /// creates new Ulid from the current time in milliseconds and entropy from
+    /// the operating system
+    ///
+    /// # Panics
+    ///
+    /// if the operating system fails to provide entropy
+    #[cfg(all(feature = "std", feature = "getrandom"))]
+    pub fn generate() -> Ulid {
+        Ulid::from_entropy(crate::clock::now_millis(), os_entropy())
+    }
+
+    /// creates `n` new Ulids like `generate`, see `generate_into`
+    ///
+    /// # Panics
+    ///
+    /// if the operating system fails to provide entropy
+    #[cfg(all(feature = "std", feature = "getrandom"))]
+    pub fn generate_batch(n: usize) -> Vec<Ulid> {
+        let mut ans = vec![Ulid([0; 16]); n];
+        Ulid::generate_into(&mut ans);
+        ans
+    }
+
+    /// fills `ulids` with new Ulids like `generate`, reading the clock once and
+    /// taking the entropy of all of them from the buffer of the current thread
+    ///
+    /// All of them share the same timestamp, and are not ordered within it.
+    ///
+    /// # Panics
+    ///
+    /// if the operating system fails to provide entropy
+    #[cfg(all(feature = "std", feature = "getrandom"))]
+    pub fn generate_into(ulids: &mut [Ulid]) {
+        Ulid::generate_into_at(crate::clock::now_millis(), ulids)
+    }
+
+    #[cfg(all(feature = "std", feature = "getrandom"))]
+    pub(crate) fn generate_into_at(timestamp: u64, ulids: &mut [Ulid]) {
+        with_os_entropy(|entropy| {
+            for ulid in ulids {
+                ulid.encode_time(timestamp);
+                ulid.encode_entropy(&mut *entropy);
+            }
+        })
+    }
+
+    /// creates new Ulid from a timestamp and 10 bytes of entropy
+    #[cfg(all(feature = "std", any(feature = "getrandom", feature = "wasm")))]
+    pub(crate) fn from_entropy(timestamp: u64, entropy: [u8; 10]) -> Ulid {
+        let mut ans = Ulid([0; 16]);
+        ans.encode_time(timestamp);
+        ans.0[6..].copy_from_slice(&entropy);
+        ans
+    }
+
+    /// encodes time in the first 6 words
+    pub fn encode_time(&mut self, timestamp: u64) {
+        let entropy = u128::from(*self) & ENTROPY_MASK;
+        *self = Ulid::from(u128::from(timestamp) << 80 | entropy);
+    }
+
+    /// encodes entropy in the last 10 words
+    pub fn encode_entropy<E>(&mut self, mut entropy: E)
+    where
+        E: EntropySource,
+    {
+        let mut bytes = [0; 10];
+        entropy.fill(&mut bytes);
+        self.0[6..].copy_from_slice(&bytes);
+    }
+
+    /// returns the Ulid with the same timestamp and the bits of the entropy in
+    /// `mask` incremented by one, or `None` if they are already at their maximum
+    fn increment_entropy(&self, mask: u128) -> Option<Ulid> {
+        let val = u128::from(*self);
+
+        if val & mask == mask {
+            None
+        } else {
+            Some(Ulid::from(val + 1))
+        }
+    }
+}
+
+#[cfg(all(feature = "std", feature = "getrandom"))]
+impl Ulid {
+    /// creates new Ulid from the current time in milliseconds and entropy
+    /// from the operating system, using a `Generator` local to the current
+    /// thread
+    ///
+    /// Needs no synchronization between threads. The Ulids are strictly
+    /// increasing within each thread, and fail with `UlidError::Overflow` like
+    /// `Generator::generate`.
+    ///
+    /// # Panics
+    ///
+    /// if the operating system fails to provide entropy
+    pub fn generate_local() -> Result<Ulid, UlidError> {
+        thread_local! {
+            static LOCAL: core::cell::RefCell<Generator> =
+                core::cell::RefCell::new(Generator::new());
+        }
+
+        LOCAL.with(|local| local.borrow_mut().generate())
+    }
+}
+
+/// returns 10 bytes of entropy from the operating system, like
+/// `with_os_entropy`
+///
+/// # Panics
+///
+/// if the operating system fails to provide entropy
+#[cfg(all(feature = "std", feature = "getrandom"))]
+pub(crate) fn os_entropy() -> [u8; 10] {
+    with_os_entropy(|source| {
+        let mut entropy = [0; 10];
+        source.fill(&mut entropy);
+        entropy
+    })
+}
+
+/// calls `f` with the `BufferedEntropy` of the current thread, which reads from
+/// the operating system 4 KiB at a time
+///
+/// Every Ulid this crate creates with entropy from the operating system takes
+/// it from here, single or in batches. Only `BufferedEntropy` itself reads from
+/// the operating system directly to refill. The buffer is refilled in a forked
+/// child, which would otherwise hand out the same bytes as the parent, which it
+/// notices from a count of forks bumped by a `pthread_atfork` handler, so
+/// creating a Ulid makes no system call outside of refills.
+///
+/// # Panics
+///
+/// if the operating system fails to provide entropy
+#[cfg(all(feature = "std", feature = "getrandom"))]
+pub(crate) fn with_os_entropy<F, T>(f: F) -> T
+where
+    F: FnOnce(&mut crate::BufferedEntropy) -> T,
+{
+    use core::cell::RefCell;
+
+    use crate::BufferedEntropy;
+
+    thread_local! {
+        static BUFFER: RefCell<Option<(usize, BufferedEntropy)>> = const { RefCell::new(None) };
+    }
+
+    let mut f = Some(f);
+
+    let buffered = BUFFER.try_with(|buffer| {
+        let forks = forks();
+        let mut buffer = buffer.borrow_mut();
+
+        let buffered = match &mut *buffer {
+            Some((owner, buffered)) if *owner == forks => buffered,
+            buffer => &mut buffer.insert((forks, BufferedEntropy::new())).1,
+        };
+
+        f.take().map(|f| f(buffered))
+    });
+
+    match buffered {
+        Ok(Some(ans)) => ans,
+        // the buffer is gone while the thread exits, so read one Ulid at a time
+        _ => f
+            .take()
+            .map(|f| f(&mut BufferedEntropy::with_capacity(10)))
+            .unwrap(),
+    }
+}
+
+/// returns how many times this process is a forked child, counting forks of
+/// forks, so the entropy buffers of the parent are not reused
+#[cfg(all(feature = "std", feature = "getrandom"))]
+fn forks() -> usize {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static FORKS: AtomicUsize = AtomicUsize::new(0);
+
+    #[cfg(unix)]
+    {
+        static REGISTER: std::sync::Once = std::sync::Once::new();
+
+        extern "C" fn child() {
+            FORKS.fetch_add(1, Ordering::Relaxed);
+        }
+
+        REGISTER.call_once(|| {
+            // only fails without memory for the handler, where the buffers
+            // are left shared with forked children
+            unsafe { libc::pthread_atfork(None, None, Some(child)) };
+        });
+    }
+
+    FORKS.load(Ordering::Relaxed)
+}
+
+#[cfg(all(feature = "std", feature = "getrandom"))]
+pub(crate) fn fill_os_entropy(entropy: &mut [u8]) {
+    getrandom::fill(entropy).expect("failed to read entropy from the operating system");
+}
+
+/// creates Ulids that are strictly increasing, including within the same
+/// millisecond
+///
+/// As in the spec, when the timestamp is not later than that of the previous
+/// Ulid, the previous entropy is incremented by one in place of drawing new
+/// entropy. This also keeps the order when the clock moves backwards, with
+/// the Ulids keeping the previous timestamp until the clock catches up.
+///
+/// The time for `now` and `generate` comes from a `Clock`, which is the
+/// `SystemClock` unless created with `Generator::with_clock`.
+///
+/// With `Generator::with_node`, the first bytes of the entropy hold a node or
+/// shard ID in place of random bytes, so nodes with different IDs never create
+/// the same Ulid.
+///
+/// What happens when the entropy overflows within a millisecond is set with
+/// `Generator::with_overflow`, failing with `UlidError::Overflow` by default.
+///
+/// `Generator::stats` counts the Ulids created and the unusual cases along the
+/// way, for exporting to a metrics system.
+#[derive(Debug, Default)]
+pub struct Generator<C = SystemClock> {
+    previous: Option<Ulid>,
+    // the first Ulid of the millisecond of `previous`, where wrapping stops
+    first: Option<Ulid>,
+    clock: C,
+    node: [u8; 8],
+    node_len: usize,
+    lane: u8,
+    lane_bits: u32,
+    overflow: OverflowPolicy,
+    stats: GeneratorStats,
+}
+
+/// counters of a `Generator`, from `Generator::stats`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct GeneratorStats {
+    /// Ulids returned, not counting creates that failed
+    pub issued: u64,
+    /// Ulids created by incrementing the previous one, as the timestamp was
+    /// not later
+    pub same_millisecond: u64,
+    /// times the entropy overflowed within a millisecond, whether that failed,
+    /// wrapped or waited as the `OverflowPolicy` says
+    pub overflows: u64,
+    /// times the timestamp was earlier than that of the previous Ulid, such as
+    /// when the clock moved backwards
+    pub clock_regressions: u64,
+}
+
+/// what a `Generator` does once the entropy of its Ulids has been incremented
+/// to the maximum within a millisecond
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum OverflowPolicy {
+    /// fails with `UlidError::Overflow`, which is the default
+    #[default]
+    Error,
+    /// waits for the clock to move past the timestamp of the previous Ulid,
+    /// for batch jobs that would rather slow down than fail
+    ///
+    /// Only applies where the Generator reads its clock, such as in `now` and
+    /// `generate`, as `create` is given the timestamp. After the clock moved
+    /// backwards this waits until it catches up.
+    Spin,
+    /// wraps the entropy around to zero and keeps the timestamp, so the Ulids
+    /// are no longer increasing within that millisecond
+    ///
+    /// Fails with `UlidError::Overflow` once the entropy climbs back to that of
+    /// the first Ulid of the millisecond, which would start repeating Ulids.
+    /// With a node or lane ID that can take only a few thousand Ulids. After
+    /// `Generator::resume_after` the first Ulid is unknown, so the Generator
+    /// fails in place of wrapping until the next millisecond.
+    Wrap,
+}
+
+impl Generator {
+    /// creates new Generator
+    pub fn new() -> Generator {
+        Generator::with_clock(SystemClock)
+    }
+}
+
+impl<C> Generator<C> {
+    /// creates new Generator reading the time from a custom clock
+    pub fn with_clock(clock: C) -> Generator<C> {
+        Generator {
+            previous: None,
+            first: None,
+            clock,
+            node: [0; 8],
+            node_len: 0,
+            lane: 0,
+            lane_bits: 0,
+            overflow: OverflowPolicy::Error,
+            stats: GeneratorStats {
+                issued: 0,
+                same_millisecond: 0,
+                overflows: 0,
+                clock_regressions: 0,
+            },
+        }
+    }
+
+    /// sets what happens when the entropy overflows within a millisecond
+    pub fn with_overflow(mut self, overflow: OverflowPolicy) -> Generator<C> {
+        self.overflow = overflow;
+        self
+    }
+
+    /// reserves the first `node.len()` bytes of the entropy for `node`, with
+    /// the rest still random
+    ///
+    /// Within a millisecond only the random bytes are incremented, so the
+    /// Generator overflows after 2^(80 - 8 * `node.len()`) Ulids. Read the ID
+    /// back with `Ulid::node_id`.
+    ///
+    /// # Panics
+    ///
+    /// if `node` is longer than 8 bytes, which would leave less than 16 bits of
+    /// entropy, or less with a lane from `with_lane`
+    pub fn with_node(mut self, node: &[u8]) -> Generator<C> {
+        assert!(node.len() <= 8, "node ID longer than 8 bytes");
+        assert!(
+            8 * node.len() as u32 + self.lane_bits <= 64,
+            "node and lane IDs longer than 64 bits"
+        );
+
+        self.node[..node.len()].copy_from_slice(node);
+        self.node_len = node.len();
+        self
+    }
+
+    /// returns the node ID set with `with_node`, which is empty by default
+    pub fn node_id(&self) -> &[u8] {
+        &self.node[..self.node_len]
+    }
+
+    /// reserves the first log2(`lanes`) bits of the random entropy, after any
+    /// node ID, for `lane`, as done by `ShardedGenerator`
+    ///
+    /// Generators with different lanes never create the same Ulid, and each is
+    /// strictly increasing on its own. Read the lane back with `Ulid::lane_id`.
+    ///
+    /// # Panics
+    ///
+    /// if `lanes` is not a power of two up to 256, if `lane` is not less than
+    /// `lanes`, or if the node and lane IDs would leave less than 16 bits of
+    /// entropy
+    pub fn with_lane(mut self, lane: usize, lanes: usize) -> Generator<C> {
+        assert!(
+            lanes.is_power_of_two() && lanes <= 256,
+            "lanes not a power of two up to 256"
+        );
+        assert!(lane < lanes, "lane out of range");
+
+        let lane_bits = lanes.trailing_zeros();
+        assert!(
+            8 * self.node_len as u32 + lane_bits <= 64,
+            "node and lane IDs longer than 64 bits"
+        );
+
+        self.lane = lane as u8;
+        self.lane_bits = lane_bits;
+        self
+    }
+
+    /// continues after `previous`, such as the last Ulid of an earlier
+    /// process, so the next Ulids are greater than it
+    pub fn resume_after(mut self, previous: Ulid) -> Generator<C> {
+        if Some(previous) > self.previous {
+            self.previous = Some(previous);
+            self.first = None;
+        }
+        self
+    }
+
+    /// returns the last Ulid created, if any
+    pub fn last(&self) -> Option<Ulid> {
+        self.previous
+    }
+
+    /// returns the counters since the Generator was created
+    pub fn stats(&self) -> GeneratorStats {
+        self.stats
+    }
+
+    /// creates the next Ulid from a timestamp and a source of entropy
+    ///
+    /// Fails with `UlidError::Overflow` once the entropy has been incremented to
+    /// the maximum within the same millisecond, or with `OverflowPolicy::Wrap`
+    /// once it gets back to where the millisecond started, and with
+    /// `UlidError::InvalidTimestamp` for a timestamp that does not fit in 48
+    /// bits.
+    pub fn create<E>(&mut self, timestamp: u64, entropy: E) -> Result<Ulid, UlidError>
+    where
+        E: EntropySource,
+    {
+        self.create_with(timestamp, |ans| ans.encode_entropy(entropy))
+    }
+
+    /// creates the next Ulid from a timestamp and 10 bytes of entropy
+    #[cfg(any(feature = "napi", feature = "uniffi"))]
+    pub(crate) fn create_from_entropy(
+        &mut self,
+        timestamp: u64,
+        entropy: [u8; 10],
+    ) -> Result<Ulid, UlidError> {
+        self.create_with(timestamp, |ans| ans.0[6..].copy_from_slice(&entropy))
+    }
+
+    /// the entropy bits that are random, after the node and lane IDs
+    fn mask(&self) -> u128 {
+        ENTROPY_MASK >> (8 * self.node_len as u32 + self.lane_bits)
+    }
+
+    fn create_with<F>(&mut self, timestamp: u64, encode_entropy: F) -> Result<Ulid, UlidError>
+    where
+        F: FnOnce(&mut Ulid),
+    {
+        let mask = self.mask();
+
+        let (same_millisecond, wrapped) = match self.previous {
+            Some(previous) if timestamp <= previous.timestamp() && timestamp >> 48 == 0 => {
+                if timestamp < previous.timestamp() {
+                    self.stats.clock_regressions += 1;
+                }
+                (true, u128::from(previous) & mask == mask)
+            }
+            _ => (false, false),
+        };
+
+        let ans = self.next(timestamp, same_millisecond, wrapped, encode_entropy);
+
+        // only Ulids actually returned count as issued
+        match ans {
+            Ok(_) => {
+                self.stats.issued += 1;
+                if same_millisecond {
+                    self.stats.same_millisecond += 1;
+                }
+                if wrapped {
+                    self.stats.overflows += 1;
+                }
+            }
+            Err(UlidError::Overflow) => self.stats.overflows += 1,
+            Err(_) => {}
+        }
+
+        ans
+    }
+
+    fn next<F>(
+        &mut self,
+        timestamp: u64,
+        same_millisecond: bool,
+        wrapped: bool,
+        encode_entropy: F,
+    ) -> Result<Ulid, UlidError>
+    where
+        F: FnOnce(&mut Ulid),
+    {
+        let (node, node_len) = (self.node, self.node_len);
+        let mask = self.mask();
+        let lane = u128::from(self.lane) << (80 - 8 * node_len as u32 - self.lane_bits);
+
+        let ans = next_after(self.previous, timestamp, mask, self.overflow, || {
+            let mut ans = Ulid([0; 16]);
+            ans.encode_time(timestamp);
+            encode_entropy(&mut ans);
+            ans.0[6..6 + node_len].copy_from_slice(&node[..node_len]);
+            Ulid::from(
+                u128::from(ans) & !(ENTROPY_MASK >> (8 * node_len)) | lane | u128::from(ans) & mask,
+            )
+        })?;
+
+        if same_millisecond {
+            // the wrapped entropy climbs back to the first Ulid of the
+            // millisecond, and everything after it was created already
+            if Some(ans) == self.first || (wrapped && self.first.is_none()) {
+                return Err(UlidError::Overflow);
+            }
+        } else {
+            self.first = Some(ans);
+        }
+
+        self.previous = Some(ans);
+        Ok(ans)
+    }
+}
+
+impl<C> Generator<C>
+where
+    C: Clock,
+{
+    /// creates the next Ulid from the current time in milliseconds and a
+    /// source of entropy
+    pub fn now<E>(&mut self, entropy: E) -> Result<Ulid, UlidError>
+    where
+        E: EntropySource,
+    {
+        let timestamp = self.timestamp();
+        self.create(timestamp, entropy)
+    }
+
+    /// creates the next Ulid from the current time in milliseconds and entropy
+    /// from the operating system
+    ///
+    /// # Panics
+    ///
+    /// if the operating system fails to provide entropy
+    #[cfg(all(feature = "std", feature = "getrandom"))]
+    pub fn generate(&mut self) -> Result<Ulid, UlidError> {
+        let timestamp = self.timestamp();
+        self.create_with(timestamp, |ans| ans.0[6..].copy_from_slice(&os_entropy()))
+    }
+
+    /// the time of the clock, after waiting for it to move past the previous
+    /// Ulid if that one overflowed and the policy is `OverflowPolicy::Spin`
+    fn timestamp(&mut self) -> u64 {
+        let mut timestamp = self.clock.now_millis();
+
+        if self.overflow == OverflowPolicy::Spin {
+            let mask = self.mask();
+
+            if let Some(previous) = self.previous {
+                if timestamp <= previous.timestamp() && u128::from(previous) & mask == mask {
+                    self.stats.overflows += 1;
+                }
+
+                while timestamp <= previous.timestamp() && u128::from(previous) & mask == mask {
+                    core::hint::spin_loop();
+                    timestamp = self.clock.now_millis();
+                }
+            }
+        }
+
+        timestamp
+    }
+}
+
+/// the Ulid following `previous` for `timestamp`, which is the previous one
+/// with the entropy bits in `mask` incremented when the timestamp is not later,
+/// and `fresh` otherwise
+///
+/// When those bits overflow they wrap to zero with `OverflowPolicy::Wrap`, and
+/// fail otherwise. Timestamps beyond 48 bits are rejected, since truncating
+/// them would break the ordering.
+fn next_after<F>(
+    previous: Option<Ulid>,
+    timestamp: u64,
+    mask: u128,
+    overflow: OverflowPolicy,
+    fresh: F,
+) -> Result<Ulid, UlidError>
+where
+    F: FnOnce() -> Ulid,
+{
+    if timestamp >> 48 != 0 {
+        return Err(UlidError::InvalidTimestamp);
+    }
+
+    match previous {
+        Some(previous) if timestamp <= previous.timestamp() => {
+            match (previous.increment_entropy(mask), overflow) {
+                (Some(ans), _) => Ok(ans),
+                (None, OverflowPolicy::Wrap) => Ok(Ulid::from(u128::from(previous) & !mask)),
+                (None, _) => Err(UlidError::Overflow),
+            }
+        }
+        _ => Ok(fresh()),
+    }
+}
+
+/// a `Generator` for many producers, sharded into lanes with the lane ID in
+/// the first bits of the random entropy
+///
+/// `generate` binds each calling thread to a free lane on its first call, and
+/// creates Ulids with the `Generator` of that lane without sharing a lock or an
+/// atomic with any other thread. The lane is given back when the thread exits,
+/// so thread pools that replace their threads do not run out of lanes, and
+/// `generate` only fails with `UlidError::NoFreeLane` while more threads than
+/// lanes are alive.
+///
+/// Other producers, such as tasks, claim a lane of their own with `claim`,
+/// which is given back when the returned `ShardedLane` is dropped. The
+/// Generator of a lane is kept between owners, so the Ulids are strictly
+/// increasing within each lane, and never the same across lanes.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct ShardedGenerator<C = SystemClock> {
+    shared: std::sync::Arc<Lanes<C>>,
+}
+
+/// the lanes of a `ShardedGenerator`, shared with its `ShardedLane`s
+#[cfg(feature = "std")]
+#[derive(Debug)]
+struct Lanes<C> {
+    clock: C,
+    lanes: usize,
+    // one bit per lane, set while the lane has an owner
+    taken: [core::sync::atomic::AtomicU64; 4],
+    // the Generators of lanes without an owner, once they had one
+    parked: Vec<std::sync::Mutex<Option<Generator<C>>>>,
+}
+
+#[cfg(feature = "std")]
+impl<C> Lanes<C> {
+    /// marks the first free lane as taken and returns it
+    fn take(&self) -> Option<usize> {
+        use core::sync::atomic::Ordering;
+
+        for (word, bits) in self.taken.iter().enumerate() {
+            let len = self.lanes.saturating_sub(64 * word).min(64);
+            let mask = match len {
+                0 => break,
+                64 => u64::MAX,
+                len => (1 << len) - 1,
+            };
+
+            let mut current = bits.load(Ordering::Relaxed);
+            while !current & mask != 0 {
+                let bit = (!current & mask).trailing_zeros();
+                match bits.compare_exchange_weak(
+                    current,
+                    current | 1 << bit,
+                    Ordering::Acquire,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => return Some(64 * word + bit as usize),
+                    Err(actual) => current = actual,
+                }
+            }
+        }
+
+        None
+    }
+
+    /// parks the Generator of `lane` for its next owner and marks it free
+    fn release(&self, lane: usize, generator: Generator<C>) {
+        use core::sync::atomic::Ordering;
+
+        *self.lock(lane) = Some(generator);
+        self.taken[lane / 64].fetch_and(!(1 << (lane % 64)), Ordering::Release);
+    }
+
+    fn lock(&self, lane: usize) -> std::sync::MutexGuard<'_, Option<Generator<C>>> {
+        match self.parked[lane].lock() {
+            Ok(parked) => parked,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ShardedGenerator {
+    /// creates new ShardedGenerator with `lanes` lanes
+    ///
+    /// # Panics
+    ///
+    /// if `lanes` is not a power of two up to 256
+    pub fn new(lanes: usize) -> ShardedGenerator {
+        ShardedGenerator::with_clock(lanes, SystemClock)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C> ShardedGenerator<C> {
+    /// creates new ShardedGenerator with `lanes` lanes, each reading the time
+    /// from a clone of `clock`
+    ///
+    /// # Panics
+    ///
+    /// if `lanes` is not a power of two up to 256
+    pub fn with_clock(lanes: usize, clock: C) -> ShardedGenerator<C> {
+        assert!(
+            lanes.is_power_of_two() && lanes <= 256,
+            "lanes must be a power of two up to 256"
+        );
+
+        ShardedGenerator {
+            shared: std::sync::Arc::new(Lanes {
+                clock,
+                lanes,
+                taken: Default::default(),
+                parked: (0..lanes).map(|_| std::sync::Mutex::new(None)).collect(),
+            }),
+        }
+    }
+
+    /// returns the number of lanes
+    pub fn lanes(&self) -> usize {
+        self.shared.lanes
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C> ShardedGenerator<C>
+where
+    C: Clone,
+{
+    /// returns the `Generator` of a free lane, for one producer to own until
+    /// it drops the `ShardedLane`, or `None` while every lane has an owner
+    pub fn claim(&self) -> Option<ShardedLane<C>> {
+        let lane = self.shared.take()?;
+        let generator = self.shared.lock(lane).take().unwrap_or_else(|| {
+            Generator::with_clock(self.shared.clock.clone()).with_lane(lane, self.shared.lanes)
+        });
+
+        Some(ShardedLane {
+            generator: Some(generator),
+            lane,
+            shared: self.shared.clone(),
+        })
+    }
+
+    /// returns the free lanes, in order of their lane IDs, to move them to
+    /// their producers
+    pub fn into_lanes(self) -> Vec<ShardedLane<C>> {
+        core::iter::from_fn(|| self.claim()).collect()
+    }
+}
+
+#[cfg(all(feature = "std", feature = "getrandom"))]
+impl<C> ShardedGenerator<C>
+where
+    C: Clock + Clone + 'static,
+{
+    /// creates the next Ulid of the lane of the current thread, from the
+    /// current time and entropy from the operating system
+    ///
+    /// The first call on a thread claims a lane for it, failing with
+    /// `UlidError::NoFreeLane` while every lane has an owner, and the lane is
+    /// given back when the thread exits.
+    ///
+    /// # Panics
+    ///
+    /// if the operating system fails to provide entropy
+    pub fn generate(&self) -> Result<Ulid, UlidError> {
+        use core::cell::RefCell;
+
+        thread_local! {
+            static BOUND: RefCell<Vec<Box<dyn BoundLane>>> = const { RefCell::new(Vec::new()) };
+        }
+
+        let shared = std::sync::Arc::as_ptr(&self.shared) as *const ();
+
+        let ans = BOUND.try_with(|bound| {
+            let mut bound = bound.borrow_mut();
+
+            let index = match bound.iter().position(|lane| lane.is_from(shared)) {
+                Some(index) => index,
+                None => {
+                    // lanes of dropped ShardedGenerators are of no more use
+                    bound.retain(|lane| !lane.is_orphaned());
+                    bound.push(Box::new(self.claim().ok_or(UlidError::NoFreeLane)?));
+                    bound.len() - 1
+                }
+            };
+
+            let lane = bound[index].as_any_mut().downcast_mut::<ShardedLane<C>>();
+            lane.expect("lane bound to another clock type").generate()
+        });
+
+        match ans {
+            Ok(ans) => ans,
+            // the bound lanes are gone while the thread exits, so borrow a lane
+            // for this one Ulid
+            Err(_) => self.claim().ok_or(UlidError::NoFreeLane)?.generate(),
+        }
+    }
+}
+
+/// a lane of a `ShardedGenerator` bound to a thread by `generate`, for any
+/// clock type
+#[cfg(all(feature = "std", feature = "getrandom"))]
+trait BoundLane {
+    /// whether the lane is of the ShardedGenerator sharing `shared`
+    fn is_from(&self, shared: *const ()) -> bool;
+
+    /// whether nothing else holds the lanes, as the ShardedGenerator and its
+    /// other lanes have been dropped
+    fn is_orphaned(&self) -> bool;
+
+    fn as_any_mut(&mut self) -> &mut dyn core::any::Any;
+}
+
+#[cfg(all(feature = "std", feature = "getrandom"))]
+impl<C> BoundLane for ShardedLane<C>
+where
+    C: 'static,
+{
+    fn is_from(&self, shared: *const ()) -> bool {
+        std::sync::Arc::as_ptr(&self.shared) as *const () == shared
+    }
+
+    fn is_orphaned(&self) -> bool {
+        std::sync::Arc::strong_count(&self.shared) == 1
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn core::any::Any {
+        self
+    }
+}
+
+/// the `Generator` of a lane claimed from a `ShardedGenerator`, which gives
+/// the lane back when dropped
+///
+/// Dereferences to the `Generator`.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct ShardedLane<C = SystemClock> {
+    // only taken out on drop
+    generator: Option<Generator<C>>,
+    lane: usize,
+    shared: std::sync::Arc<Lanes<C>>,
+}
+
+#[cfg(feature = "std")]
+impl<C> ShardedLane<C> {
+    /// returns the lane ID
+    pub fn lane(&self) -> usize {
+        self.lane
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C> core::ops::Deref for ShardedLane<C> {
+    type Target = Generator<C>;
+
+    fn deref(&self) -> &Generator<C> {
+        self.generator.as_ref().unwrap()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C> core::ops::DerefMut for ShardedLane<C> {
+    fn deref_mut(&mut self) -> &mut Generator<C> {
+        self.generator.as_mut().unwrap()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C> Drop for ShardedLane<C> {
+    fn drop(&mut self) {
+        if let Some(generator) = self.generator.take() {
+            self.shared.release(self.lane, generator);
+        }
+    }
+}
+
+/// an endless iterator of new Ulids for the current time, with entropy from the
+/// operating system
+///
+/// With `UlidIter::monotonic` the Ulids are strictly increasing like those from
+/// `Generator`, and in the unlikely case the entropy overflows within a
+/// millisecond the iterator waits for the next one, as with
+/// `OverflowPolicy::Spin`.
+///
+/// # Panics
+///
+/// if the operating system fails to provide entropy
+#[cfg(all(feature = "std", feature = "getrandom"))]
+#[derive(Debug, Default)]
+pub struct UlidIter<C = SystemClock> {
+    generator: Option<Generator<C>>,
+}
+
+#[cfg(all(feature = "std", feature = "getrandom"))]
+impl UlidIter {
+    /// creates new UlidIter, where every Ulid is created like `Ulid::generate`
+    pub fn new() -> UlidIter {
+        UlidIter { generator: None }
+    }
+
+    /// creates new UlidIter, where the Ulids come from a new `Generator`
+    pub fn monotonic() -> UlidIter {
+        UlidIter::monotonic_with(Generator::new())
+    }
+}
+
+#[cfg(all(feature = "std", feature = "getrandom"))]
+impl<C> UlidIter<C> {
+    /// creates new UlidIter, where the Ulids come from `generator`, which waits
+    /// for the next millisecond when its entropy overflows
+    pub(crate) fn monotonic_with(generator: Generator<C>) -> UlidIter<C> {
+        UlidIter {
+            generator: Some(generator.with_overflow(OverflowPolicy::Spin)),
+        }
+    }
+}
+
+#[cfg(all(feature = "std", feature = "getrandom"))]
+impl<C> Iterator for UlidIter<C>
+where
+    C: Clock,
+{
+    type Item = Ulid;
+
+    fn next(&mut self) -> Option<Ulid> {
+        match &mut self.generator {
+            // with OverflowPolicy::Spin this only fails for a clock past the
+            // 48 bit timestamp
+            Some(generator) => generator.generate().ok(),
+            None => Some(Ulid::generate()),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+#[cfg(all(feature = "std", feature = "getrandom"))]
+impl<C> core::iter::FusedIterator for UlidIter<C> where C: Clock {}
+
+/// a `Generator` that can be shared between threads, which updates the
+/// previous Ulid with a compare and swap in place of taking a lock
+///
+/// Uses a 128 bit atomic from `portable-atomic`, which is lock-free where the
+/// CPU has a 128 bit compare and swap, such as x86_64 with `cmpxchg16b` and
+/// aarch64, and falls back to a lock otherwise. See
+/// `AtomicGenerator::is_lock_free`.
+#[cfg(feature = "atomic")]
+#[derive(Debug, Default)]
+pub struct AtomicGenerator {
+    // starts at the nil Ulid, which only matters for a timestamp of 0
+    previous: portable_atomic::AtomicU128,
+}
+
+#[cfg(feature = "atomic")]
+impl AtomicGenerator {
+    /// creates new AtomicGenerator
+    pub const fn new() -> AtomicGenerator {
+        AtomicGenerator {
+            previous: portable_atomic::AtomicU128::new(0),
+        }
+    }
+
+    /// returns whether creating Ulids is lock-free on this CPU
+    pub fn is_lock_free() -> bool {
+        portable_atomic::AtomicU128::is_lock_free()
+    }
+
+    /// creates the next Ulid from a timestamp and a source of entropy, like
+    /// `Generator::create`
+    ///
+    /// The entropy is drawn before knowing whether it is needed.
+    pub fn create<E>(&self, timestamp: u64, entropy: E) -> Result<Ulid, UlidError>
+    where
+        E: EntropySource,
+    {
+        self.create_from(timestamp, Ulid::new(timestamp, entropy))
+    }
+
+    /// creates the next Ulid from the current time in milliseconds and entropy
+    /// from the operating system, like `Generator::generate`
+    ///
+    /// # Panics
+    ///
+    /// if the operating system fails to provide entropy
+    #[cfg(all(feature = "std", feature = "getrandom"))]
+    pub fn generate(&self) -> Result<Ulid, UlidError> {
+        let timestamp = crate::clock::now_millis();
+        self.create_from(timestamp, Ulid::from_entropy(timestamp, os_entropy()))
+    }
+
+    fn create_from(&self, timestamp: u64, fresh: Ulid) -> Result<Ulid, UlidError> {
+        use core::sync::atomic::Ordering;
+
+        let mut previous = self.previous.load(Ordering::Acquire);
+
+        loop {
+            let ans = next_after(
+                Some(Ulid::from(previous)),
+                timestamp,
+                ENTROPY_MASK,
+                OverflowPolicy::Error,
+                || fresh,
+            )?;
+
+            match self.previous.compare_exchange_weak(
+                previous,
+                u128::from(ans),
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return Ok(ans),
+                Err(current) => previous = current,
+            }
+        }
+    }
+}
+
+/// a `Generator` for async code, which sleeps until the next millisecond with
+/// tokio when the entropy overflows, in place of failing or spinning
+///
+/// Shared by reference between tasks, with the lock never held across an
+/// `await`. The overflow policy of a Generator converted with `From` is
+/// ignored, except that `OverflowPolicy::Wrap` wraps the entropy once before
+/// sleeping.
+#[cfg(feature = "tokio")]
+#[derive(Debug, Default)]
+pub struct AsyncGenerator<C = SystemClock> {
+    generator: std::sync::Mutex<Generator<C>>,
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncGenerator {
+    /// creates new AsyncGenerator
+    pub fn new() -> AsyncGenerator {
+        AsyncGenerator::from(Generator::new())
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<C> From<Generator<C>> for AsyncGenerator<C> {
+    fn from(generator: Generator<C>) -> Self {
+        AsyncGenerator {
+            generator: std::sync::Mutex::new(generator),
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<C> AsyncGenerator<C> {
+    /// returns the counters since the Generator was created, see
+    /// `Generator::stats`
+    pub fn stats(&self) -> GeneratorStats {
+        match self.generator.lock() {
+            Ok(generator) => generator.stats(),
+            Err(poisoned) => poisoned.into_inner().stats(),
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<C> AsyncGenerator<C>
+where
+    C: Clock,
+{
+    /// creates the next Ulid from the current time in milliseconds and a
+    /// source of entropy, like `Generator::now`
+    pub async fn now<E>(&self, mut entropy: E) -> Result<Ulid, UlidError>
+    where
+        E: EntropySource,
+    {
+        self.create_with(|ans| {
+            let mut bytes = [0; 10];
+            entropy.fill(&mut bytes);
+            ans.0[6..].copy_from_slice(&bytes);
+        })
+        .await
+    }
+
+    /// creates the next Ulid from the current time in milliseconds and entropy
+    /// from the operating system, like `Generator::generate`
+    ///
+    /// # Panics
+    ///
+    /// if the operating system fails to provide entropy
+    pub async fn generate(&self) -> Result<Ulid, UlidError> {
+        self.create_with(|ans| ans.0[6..].copy_from_slice(&os_entropy()))
+            .await
+    }
+
+    async fn create_with<F>(&self, mut encode_entropy: F) -> Result<Ulid, UlidError>
+    where
+        F: FnMut(&mut Ulid),
+    {
+        loop {
+            let wait = {
+                let mut generator = match self.generator.lock() {
+                    Ok(generator) => generator,
+                    Err(poisoned) => poisoned.into_inner(),
+                };
+
+                let timestamp = generator.clock.now_millis();
+
+                match generator.create_with(timestamp, &mut encode_entropy) {
+                    // the previous Ulid is at or after `timestamp`
+                    Err(UlidError::Overflow) => generator
+                        .previous
+                        .map_or(1, |previous| previous.timestamp() + 1 - timestamp),
+                    ans => return ans,
+                }
+            };
+
+            tokio::time::sleep(std::time::Duration::from_millis(wait)).await;
+        }
+    }
+}
+
+/// a `Generator` with entropy from a ChaCha20 rng seeded by the caller, so the
+/// same seed and the same times give the same Ulids
+///
+/// Meant for snapshot tests and replaying, the Ulids are only as unpredictable
+/// as the seed.
+#[cfg(feature = "seeded")]
+#[derive(Debug)]
+pub struct SeededGenerator<C = SystemClock> {
+    generator: Generator<C>,
+    rng: rand_chacha::ChaCha20Rng,
+}
+
+#[cfg(feature = "seeded")]
+impl<C> SeededGenerator<C> {
+    /// creates new SeededGenerator from a 32 byte seed, reading the time from
+    /// `clock`
+    pub fn from_seed(seed: [u8; 32], clock: C) -> SeededGenerator<C> {
+        use rand_core::SeedableRng;
+
+        SeededGenerator {
+            generator: Generator::with_clock(clock),
+            rng: rand_chacha::ChaCha20Rng::from_seed(seed),
+        }
+    }
+
+    /// creates new SeededGenerator from a `u64` seed, reading the time from
+    /// `clock`
+    pub fn seed_from_u64(seed: u64, clock: C) -> SeededGenerator<C> {
+        use rand_core::SeedableRng;
+
+        SeededGenerator {
+            generator: Generator::with_clock(clock),
+            rng: rand_chacha::ChaCha20Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// creates the next Ulid from a timestamp, like `Generator::create`
+    pub fn create(&mut self, timestamp: u64) -> Result<Ulid, UlidError> {
+        self.generator
+            .create(timestamp, crate::RngEntropy(&mut self.rng))
+    }
+}
+
+#[cfg(feature = "seeded")]
+impl<C> SeededGenerator<C>
+where
+    C: Clock,
+{
+    /// creates the next Ulid from the time of the clock
+    pub fn generate(&mut self) -> Result<Ulid, UlidError> {
+        let timestamp = self.generator.timestamp();
+        self.create(timestamp)
+    }
+}
@@ -0,0 +1,128 @@
+//! typed Ulids, so IDs of different entities cannot be mixed up
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::str::FromStr;
+
+use crate::{Ulid, UlidError};
+
+/// a Ulid tagged with the type of entity it identifies, so that an
+/// `Id<User>` is not accepted where an `Id<Order>` is expected
+///
+/// Has the layout of `Ulid`, and encodes, orders and hashes like the Ulid it
+/// holds. `T` is only a marker, so `Id<T>` is `Copy`, `Send` and `Sync` for
+/// any `T`. The database integrations are implemented for it as for `Ulid`.
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Binary),
+    diesel(sql_type = diesel::sql_types::Uuid)
+)]
+pub struct Id<T: ?Sized>(Ulid, PhantomData<fn() -> T>);
+
+impl<T: ?Sized> Id<T> {
+    /// creates new Id from a Ulid
+    pub const fn from_ulid(ulid: Ulid) -> Id<T> {
+        Id(ulid, PhantomData)
+    }
+
+    /// returns the Ulid
+    pub const fn ulid(self) -> Ulid {
+        self.0
+    }
+
+    /// changes the entity type, for IDs shared between entities
+    pub const fn cast<U: ?Sized>(self) -> Id<U> {
+        Id(self.0, PhantomData)
+    }
+}
+
+impl<T: ?Sized> Deref for Id<T> {
+    type Target = Ulid;
+
+    fn deref(&self) -> &Ulid {
+        &self.0
+    }
+}
+
+impl<T: ?Sized> Clone for Id<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for Id<T> {}
+
+impl<T: ?Sized> PartialEq for Id<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: ?Sized> Eq for Id<T> {}
+
+impl<T: ?Sized> PartialOrd for Id<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: ?Sized> Ord for Id<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<T: ?Sized> Hash for Id<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Id").field(&self.0).finish()
+    }
+}
+
+impl<T: ?Sized> fmt::Display for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: ?Sized> FromStr for Id<T> {
+    type Err = UlidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ulid::unmarshal(s).map(Id::from_ulid)
+    }
+}
+
+impl<T: ?Sized> From<Ulid> for Id<T> {
+    fn from(ulid: Ulid) -> Self {
+        Id::from_ulid(ulid)
+    }
+}
+
+impl<T: ?Sized> From<Id<T>> for Ulid {
+    fn from(id: Id<T>) -> Self {
+        id.0
+    }
+}
+
+impl<T: ?Sized> PartialEq<Ulid> for Id<T> {
+    fn eq(&self, other: &Ulid) -> bool {
+        self.0 == *other
+    }
+}
+
+impl<T: ?Sized> PartialEq<Id<T>> for Ulid {
+    fn eq(&self, other: &Id<T>) -> bool {
+        *self == other.0
+    }
+}
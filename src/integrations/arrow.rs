@@ -0,0 +1,61 @@
+//! Apache Arrow helpers, for columns of Ulids in analytics pipelines such as
+//! those writing Parquet
+//!
+//! `FixedSizeBinary(16)` columns keep the 16 bytes and sort like the Ulids,
+//! while `Utf8` columns hold the 26 character string form. Nulls read back as
+//! `None`.
+
+use core::convert::TryFrom;
+
+use arrow_array::builder::{FixedSizeBinaryBuilder, StringBuilder};
+use arrow_array::{FixedSizeBinaryArray, StringArray};
+
+use crate::{Ulid, UlidError};
+
+/// builds a `FixedSizeBinary(16)` array of the 16 bytes of each Ulid
+pub fn to_binary_array(ulids: &[Ulid]) -> FixedSizeBinaryArray {
+    let mut builder = FixedSizeBinaryBuilder::with_capacity(ulids.len(), 16);
+
+    for ulid in ulids {
+        // every value has the 16 bytes the builder was created with
+        builder.append_value(ulid.0).unwrap();
+    }
+
+    builder.finish()
+}
+
+/// reads the Ulids from a `FixedSizeBinary(16)` array, failing with
+/// `UlidError::InvalidBufferLength` for another width
+pub fn from_binary_array(array: &FixedSizeBinaryArray) -> Result<Vec<Option<Ulid>>, UlidError> {
+    if array.value_length() != 16 {
+        return Err(UlidError::InvalidBufferLength {
+            len: array.value_length() as usize,
+        });
+    }
+
+    array
+        .iter()
+        .map(|value| value.map(Ulid::try_from).transpose())
+        .collect()
+}
+
+/// builds a `Utf8` array of the string form of each Ulid
+pub fn to_string_array(ulids: &[Ulid]) -> StringArray {
+    let mut builder = StringBuilder::with_capacity(ulids.len(), 26 * ulids.len());
+
+    for ulid in ulids {
+        // marshal only ever produces ASCII
+        builder.append_value(core::str::from_utf8(&ulid.marshal()).unwrap());
+    }
+
+    builder.finish()
+}
+
+/// parses the Ulids from a `Utf8` array, failing on the first string that is
+/// not a Ulid
+pub fn from_string_array(array: &StringArray) -> Result<Vec<Option<Ulid>>, UlidError> {
+    array
+        .iter()
+        .map(|value| value.map(Ulid::unmarshal).transpose())
+        .collect()
+}
@@ -0,0 +1,25 @@
+//! async-graphql support, exposing Ulids as a `Ulid` scalar holding the string
+//! form, for field and argument types
+
+use async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+
+use crate::Ulid;
+
+/// a `Ulid` scalar, in its 26 character string form
+#[Scalar(name = "Ulid", specified_by_url = "https://github.com/ulid/spec")]
+impl ScalarType for Ulid {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => Ok(Ulid::unmarshal(s)?),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        matches!(value, Value::String(s) if Ulid::unmarshal(s).is_ok())
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
@@ -0,0 +1,29 @@
+//! borsh support, encoding Ulids as their 16 raw bytes
+
+use crate::{Id, Ulid};
+
+impl borsh::BorshSerialize for Ulid {
+    fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
+        writer.write_all(&self.0)
+    }
+}
+
+impl borsh::BorshDeserialize for Ulid {
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let mut val = [0; 16];
+        reader.read_exact(&mut val)?;
+        Ok(Ulid(val))
+    }
+}
+
+impl<T: ?Sized> borsh::BorshSerialize for Id<T> {
+    fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
+        self.ulid().serialize(writer)
+    }
+}
+
+impl<T: ?Sized> borsh::BorshDeserialize for Id<T> {
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        Ulid::deserialize_reader(reader).map(Id::from_ulid)
+    }
+}
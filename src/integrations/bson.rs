@@ -0,0 +1,84 @@
+//! serde helpers for MongoDB, to use with `#[serde(with = "...")]` on a `Ulid`
+//! field
+//!
+//! Both keep the order of Ulids in MongoDB indexes, as binaries compare by
+//! their bytes and strings by their characters.
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Event {
+//!     #[serde(rename = "_id", with = "ulid_rs::bson::binary")]
+//!     id: Ulid,
+//!     #[serde(with = "ulid_rs::bson::string")]
+//!     parent: Ulid,
+//! }
+//! ```
+
+/// stores a Ulid as a BSON binary of subtype 4, the UUID subtype
+///
+/// Deserializes from binaries of the UUID and generic subtypes.
+pub mod binary {
+    use core::convert::TryFrom;
+
+    use bson::spec::BinarySubtype;
+    use bson::Binary;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::Ulid;
+
+    /// serializes as a BSON binary of the UUID subtype
+    pub fn serialize<S>(ulid: &Ulid, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Binary {
+            subtype: BinarySubtype::Uuid,
+            bytes: ulid.0.to_vec(),
+        }
+        .serialize(serializer)
+    }
+
+    /// deserializes from a BSON binary of the UUID or generic subtype
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Ulid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let binary = Binary::deserialize(deserializer)?;
+
+        match binary.subtype {
+            BinarySubtype::Uuid | BinarySubtype::Generic => {
+                Ulid::try_from(&binary.bytes[..]).map_err(D::Error::custom)
+            }
+            subtype => Err(D::Error::custom(format_args!(
+                "unexpected binary subtype {:?} for a ulid",
+                subtype
+            ))),
+        }
+    }
+}
+
+/// stores a Ulid as its 26 character string form
+pub mod string {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::Ulid;
+
+    /// serializes as the string form
+    pub fn serialize<S>(ulid: &Ulid, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(ulid)
+    }
+
+    /// deserializes from the string form
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Ulid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ulid::unmarshal(s).map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,9 @@
+//! bytemuck support, for casting slices of Ulids to and from bytes without
+//! copying
+
+use crate::Ulid;
+
+// Safety: Ulid is `repr(transparent)` over `[u8; 16]`, for which every bit
+// pattern, including all zeros, is valid, with no padding
+unsafe impl bytemuck::Zeroable for Ulid {}
+unsafe impl bytemuck::Pod for Ulid {}
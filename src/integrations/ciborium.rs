@@ -0,0 +1,46 @@
+//! CBOR support for ciborium
+//!
+//! With the `serde` impls ciborium already writes a Ulid as a 16 byte byte
+//! string, 17 bytes in all. The `tagged` serde helpers also mark it with the
+//! CBOR tag `TAG`, for readers that do not know the schema.
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Reading {
+//!     #[serde(with = "ulid_rs::ciborium::tagged")]
+//!     id: Ulid,
+//!     celsius: f32,
+//! }
+//! ```
+
+/// the CBOR tag for Ulids, an ASCII `ULID` in the first come first served
+/// range, which is not registered with IANA
+pub const TAG: u64 = 0x554C_4944;
+
+/// writes a Ulid as a byte string with the CBOR tag `TAG`
+///
+/// Reading accepts the byte string with that tag or with no tag, so that the
+/// tag can be added to existing messages.
+pub mod tagged {
+    use ciborium::tag::Accepted;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::TAG;
+    use crate::Ulid;
+
+    /// serializes as a tagged byte string
+    pub fn serialize<S>(ulid: &Ulid, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Accepted::<_, TAG>(*ulid).serialize(serializer)
+    }
+
+    /// deserializes from a byte string, tagged with `TAG` or untagged
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Ulid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Accepted::<Ulid, TAG>::deserialize(deserializer).map(|tagged| tagged.0)
+    }
+}
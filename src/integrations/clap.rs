@@ -0,0 +1,55 @@
+//! clap support, so arguments can be declared as `#[arg(value_parser)] id: Ulid`
+//!
+//! Errors name the first invalid character and its position.
+
+use clap::builder::{TypedValueParser, ValueParserFactory};
+use clap::error::ErrorKind;
+use clap::{Arg, Command, Error};
+use std::ffi::OsStr;
+
+use crate::{Ulid, UlidError};
+
+/// clap value parser for Ulids, whose errors name the first invalid character
+/// and its position
+///
+/// Used by `value_parser!(Ulid)` and `#[arg(value_parser)]`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UlidValueParser;
+
+impl TypedValueParser for UlidValueParser {
+    type Value = Ulid;
+
+    fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<Ulid, Error> {
+        let value = value.to_string_lossy();
+
+        Ulid::unmarshal(value.as_bytes()).map_err(|e| {
+            let reason = match e {
+                UlidError::InvalidLength { .. } => {
+                    format!("expected 26 characters, found {}", value.chars().count())
+                }
+                // the position in characters rather than bytes
+                UlidError::InvalidCharacter { index, .. } => format!(
+                    "invalid character '{}' at position {}",
+                    value[index..].chars().next().unwrap_or_default(),
+                    value[..index].chars().count()
+                ),
+                e => e.to_string(),
+            };
+            let arg = arg.map(|arg| format!(" for '{}'", arg)).unwrap_or_default();
+
+            Error::raw(
+                ErrorKind::ValueValidation,
+                format!("invalid value '{}'{}: {}\n", value, arg, reason),
+            )
+            .with_cmd(cmd)
+        })
+    }
+}
+
+impl ValueParserFactory for Ulid {
+    type Parser = UlidValueParser;
+
+    fn value_parser() -> UlidValueParser {
+        UlidValueParser
+    }
+}
@@ -0,0 +1,81 @@
+//! Diesel support, for `Binary` columns on every backend and `Uuid` columns on
+//! Postgres
+//!
+//! `AsExpression` and `FromSqlRow` are derived on `Ulid` and `Id` themselves.
+
+use std::convert::TryFrom;
+use std::io::Write;
+
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::{Pg, PgValue};
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::{Binary, Uuid};
+
+use crate::{Id, Ulid};
+
+impl<DB> ToSql<Binary, DB> for Ulid
+where
+    DB: Backend,
+    [u8]: ToSql<Binary, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        <[u8] as ToSql<Binary, DB>>::to_sql(&self.0, out)
+    }
+}
+
+impl<DB> FromSql<Binary, DB> for Ulid
+where
+    DB: Backend,
+    Vec<u8>: FromSql<Binary, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        let bytes = <Vec<u8> as FromSql<Binary, DB>>::from_sql(bytes)?;
+        Ok(Ulid::try_from(&bytes[..])?)
+    }
+}
+
+impl ToSql<Uuid, Pg> for Ulid {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(&self.0)?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Uuid, Pg> for Ulid {
+    fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+        Ok(Ulid::try_from(value.as_bytes())?)
+    }
+}
+
+impl<T: ?Sized, DB> ToSql<Binary, DB> for Id<T>
+where
+    DB: Backend,
+    Ulid: ToSql<Binary, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        <Ulid as ToSql<Binary, DB>>::to_sql(self, out)
+    }
+}
+
+impl<T: ?Sized, DB> FromSql<Binary, DB> for Id<T>
+where
+    DB: Backend,
+    Ulid: FromSql<Binary, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        <Ulid as FromSql<Binary, DB>>::from_sql(bytes).map(Id::from_ulid)
+    }
+}
+
+impl<T: ?Sized> ToSql<Uuid, Pg> for Id<T> {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <Ulid as ToSql<Uuid, Pg>>::to_sql(self, out)
+    }
+}
+
+impl<T: ?Sized> FromSql<Uuid, Pg> for Id<T> {
+    fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+        <Ulid as FromSql<Uuid, Pg>>::from_sql(value).map(Id::from_ulid)
+    }
+}
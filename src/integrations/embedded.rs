@@ -0,0 +1,22 @@
+//! heapless and defmt support, for formatting Ulids on embedded targets without
+//! an allocator
+
+use crate::Ulid;
+
+impl From<Ulid> for heapless::String<26> {
+    fn from(ulid: Ulid) -> Self {
+        let mut ans = heapless::String::new();
+        // 26 characters always fit
+        ulid.write_to(&mut ans).unwrap();
+        ans
+    }
+}
+
+/// logs the 26 character string form
+impl defmt::Format for Ulid {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        let chars = self.marshal();
+        // marshal only ever produces bytes from ENCODING, which is ASCII
+        defmt::write!(f, "{=str}", core::str::from_utf8(&chars).unwrap())
+    }
+}
@@ -0,0 +1,16 @@
+//! fake support, so `#[derive(Dummy)]` fixtures can have Ulid fields
+//!
+//! The Ulids are fully random, timestamp included, and reproducible with a
+//! seeded rng.
+
+use fake::{Dummy, Faker, Rng};
+
+use crate::Ulid;
+
+/// a fully random Ulid, timestamp included, so that seeded fixtures are
+/// reproducible
+impl Dummy<Faker> for Ulid {
+    fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+        Ulid::from(rng.random::<u128>())
+    }
+}
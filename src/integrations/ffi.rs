@@ -0,0 +1,72 @@
+//! C bindings
+//!
+//! A Ulid crosses the boundary as its 16 bytes, the way libuuid passes
+//! `uuid_t`. `include/ulid.h` declares these functions for C and C++, and is
+//! generated from this file with `cbindgen --config cbindgen.toml
+//! --output include/ulid.h`, which CI checks is up to date. Build the library
+//! with `cargo rustc --release --lib --features ffi --crate-type cdylib` (or
+//! `staticlib`). `tests/ffi.c` links against it and is run in CI.
+
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+
+use crate::Ulid;
+
+/// a Ulid as its 16 bytes, most significant first
+#[allow(non_camel_case_types)]
+pub type ulid_t = [u8; 16];
+
+/// writes a new Ulid for the current time, with entropy from the operating
+/// system, into `out`
+///
+/// # Safety
+///
+/// `out` must be valid for writes of 16 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ulid_new(out: *mut u8) {
+    ptr::write(out as *mut ulid_t, Ulid::generate().into());
+}
+
+/// writes the 26 character string form of `ulid` into `buf`, followed by a
+/// NUL terminator
+///
+/// # Safety
+///
+/// `ulid` must be valid for reads of 16 bytes, and `buf` for writes of 27
+/// bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ulid_to_string(ulid: *const u8, buf: *mut c_char) {
+    let text = Ulid::from(ptr::read(ulid as *const ulid_t)).marshal();
+    ptr::copy_nonoverlapping(text.as_ptr() as *const c_char, buf, 26);
+    *buf.add(26) = 0;
+}
+
+/// parses the NUL terminated string `s` into `out`
+///
+/// Returns 0 on success, and -1 if `s` is not a valid Ulid, in which case
+/// `out` is left untouched.
+///
+/// # Safety
+///
+/// `s` must point to a NUL terminated string, and `out` must be valid for
+/// writes of 16 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ulid_parse(s: *const c_char, out: *mut u8) -> c_int {
+    match Ulid::unmarshal(std::ffi::CStr::from_ptr(s).to_bytes()) {
+        Ok(ulid) => {
+            ptr::write(out as *mut ulid_t, ulid.into());
+            0
+        }
+        Err(_) => -1,
+    }
+}
+
+/// returns the timestamp of `ulid` in milliseconds
+///
+/// # Safety
+///
+/// `ulid` must be valid for reads of 16 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ulid_timestamp(ulid: *const u8) -> u64 {
+    Ulid::from(ptr::read(ulid as *const ulid_t)).timestamp()
+}
@@ -0,0 +1,55 @@
+//! FlatBuffers support, for Ulids as the 16 byte struct in `proto/ulid.fbs`
+//!
+//! A Ulid has the layout of that struct and an alignment of 1, so reading a
+//! field or a vector of them returns references into the buffer without
+//! copying, wherever the field is placed.
+//!
+//! ```ignore
+//! let id: Option<&Ulid> = table.get::<Ulid>(VT_ID, None);
+//! builder.push_slot_always::<&Ulid>(VT_ID, &id);
+//! ```
+
+use flatbuffers::{
+    follow_cast_ref, Follow, InvalidFlatbuffer, Push, PushAlignment, SimpleToVerifyInSlice,
+    Verifiable, Verifier,
+};
+
+use crate::Ulid;
+
+impl<'a> Follow<'a> for Ulid {
+    type Inner = &'a Ulid;
+
+    unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        <&'a Ulid>::follow(buf, loc)
+    }
+}
+
+impl<'a> Follow<'a> for &'a Ulid {
+    type Inner = &'a Ulid;
+
+    unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        // Ulid is repr(transparent) over [u8; 16], so it has an alignment of 1
+        // and any 16 bytes are a valid Ulid
+        follow_cast_ref::<Ulid>(buf, loc)
+    }
+}
+
+impl Push for Ulid {
+    type Output = Ulid;
+
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        dst[..16].copy_from_slice(&self.0);
+    }
+
+    fn alignment() -> PushAlignment {
+        PushAlignment::new(1)
+    }
+}
+
+impl Verifiable for Ulid {
+    fn run_verifier(v: &mut Verifier, pos: usize) -> Result<(), InvalidFlatbuffer> {
+        v.in_buffer::<Ulid>(pos)
+    }
+}
+
+impl SimpleToVerifyInSlice for Ulid {}
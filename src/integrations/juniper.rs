@@ -0,0 +1,28 @@
+//! juniper support, exposing Ulids as a `Ulid` scalar holding the string form,
+//! as with async-graphql
+
+use juniper::{graphql_scalar, InputValue, ScalarValue, Value};
+
+/// a `Ulid` scalar, in its 26 character string form
+#[graphql_scalar(
+    name = "Ulid",
+    with = ulid_scalar,
+    parse_token(String),
+    specified_by_url = "https://github.com/ulid/spec",
+)]
+type Ulid = crate::Ulid;
+
+mod ulid_scalar {
+    use super::*;
+
+    pub(super) fn to_output<S: ScalarValue>(ulid: &Ulid) -> Value<S> {
+        Value::scalar(ulid.to_string())
+    }
+
+    pub(super) fn from_input<S: ScalarValue>(value: &InputValue<S>) -> Result<Ulid, String> {
+        value
+            .as_string_value()
+            .ok_or_else(|| format!("Expected `String`, found: {}", value))
+            .and_then(|s| Ulid::unmarshal(s).map_err(|e| e.to_string()))
+    }
+}
@@ -0,0 +1,46 @@
+//! conversions from and to the 20 bytes of a KSUID
+//!
+//! The KSUID second becomes the millisecond timestamp and the first 10 payload
+//! bytes the entropy, dropping the other 6. The other way the timestamp is
+//! truncated to the second and the payload padded with zeros.
+
+use core::convert::TryFrom;
+
+use crate::{Ulid, UlidError};
+
+/// the KSUID epoch, 2014-05-13T16:53:20Z, in seconds since the unix epoch
+const EPOCH: u64 = 1_400_000_000;
+
+impl Ulid {
+    /// creates a Ulid from the 20 bytes of a KSUID
+    ///
+    /// The timestamp is the KSUID's second in milliseconds, and the entropy is
+    /// the first 10 bytes of its 16 byte payload. The other 6 payload bytes are
+    /// dropped, so KSUIDs differing only in those map to the same Ulid.
+    pub fn from_ksuid(ksuid: &[u8; 20]) -> Ulid {
+        let seconds = u32::from_be_bytes([ksuid[0], ksuid[1], ksuid[2], ksuid[3]]);
+
+        let mut ans = Ulid([0; 16]);
+        ans.encode_time((u64::from(seconds) + EPOCH) * 1000);
+        ans.0[6..].copy_from_slice(&ksuid[4..14]);
+        ans
+    }
+
+    /// converts to the 20 bytes of a KSUID, the inverse of `from_ksuid`
+    ///
+    /// The timestamp is truncated to the second, and the payload is the
+    /// entropy followed by 6 zero bytes. Fails with
+    /// `UlidError::InvalidKsuidTimestamp` for a timestamp before the KSUID
+    /// epoch in 2014, or after its last second in 2150.
+    pub fn to_ksuid(&self) -> Result<[u8; 20], UlidError> {
+        let seconds = (self.timestamp() / 1000)
+            .checked_sub(EPOCH)
+            .and_then(|seconds| u32::try_from(seconds).ok())
+            .ok_or(UlidError::InvalidKsuidTimestamp)?;
+
+        let mut ans = [0; 20];
+        ans[..4].copy_from_slice(&seconds.to_be_bytes());
+        ans[4..14].copy_from_slice(&self.0[6..]);
+        Ok(ans)
+    }
+}
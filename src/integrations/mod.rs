@@ -0,0 +1,83 @@
+//! integrations with other crates and languages, each behind the cargo feature
+//! of the same name
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "async-graphql")]
+mod async_graphql;
+#[cfg(feature = "borsh")]
+mod borsh;
+#[cfg(feature = "bson")]
+pub mod bson;
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
+#[cfg(feature = "ciborium")]
+pub mod ciborium;
+#[cfg(feature = "clap")]
+pub(crate) mod clap;
+#[cfg(feature = "diesel")]
+mod diesel;
+#[cfg(feature = "embedded")]
+mod embedded;
+#[cfg(feature = "fake")]
+mod fake;
+#[cfg(feature = "ffi")]
+pub(crate) mod ffi;
+#[cfg(feature = "flatbuffers")]
+mod flatbuffers;
+#[cfg(feature = "juniper")]
+mod juniper;
+#[cfg(feature = "ksuid")]
+mod ksuid;
+#[cfg(feature = "napi")]
+pub(crate) mod napi;
+#[cfg(feature = "objectid")]
+mod objectid;
+#[cfg(feature = "opentelemetry")]
+mod opentelemetry;
+#[cfg(feature = "postgres")]
+mod postgres;
+#[cfg(feature = "prost")]
+pub mod prost;
+#[cfg(feature = "python")]
+pub(crate) mod python;
+#[cfg(feature = "rand")]
+mod rand;
+#[cfg(feature = "rayon")]
+mod rayon;
+#[cfg(feature = "redis")]
+mod redis;
+#[cfg(feature = "rmp")]
+pub mod rmp;
+#[cfg(feature = "rocket")]
+mod rocket;
+#[cfg(feature = "rusqlite")]
+pub(crate) mod rusqlite;
+#[cfg(feature = "schemars")]
+mod schemars;
+#[cfg(feature = "scylla")]
+mod scylla;
+#[cfg(feature = "sea-orm")]
+mod sea_orm;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "sqlx")]
+pub(crate) mod sqlx;
+#[cfg(feature = "tower")]
+pub(crate) mod tower;
+#[cfg(feature = "uniffi")]
+pub(crate) mod uniffi;
+#[cfg(feature = "utoipa")]
+mod utoipa;
+#[cfg(feature = "uuid")]
+mod uuid;
+#[cfg(feature = "valuable")]
+mod valuable;
+#[cfg(feature = "wasm")]
+pub(crate) mod wasm;
+
+/// pattern for the canonical string form, used by the schema integrations.
+///
+/// The first character carries only 3 bits of the 48 bit timestamp.
+#[cfg(any(feature = "schemars", feature = "utoipa"))]
+static PATTERN: &str = "^[0-7][0-9A-HJKMNP-TV-Z]{25}$";
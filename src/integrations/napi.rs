@@ -0,0 +1,98 @@
+//! Node.js bindings through napi-rs
+//!
+//! Mirrors the API of the `ulid` npm package, with `ulid()`, `decodeTime()`
+//! and `monotonicFactory()`, along with `parse()` returning the 16 bytes. Build
+//! with `cargo rustc --release --lib --features napi --crate-type cdylib` and
+//! load the library renamed to `.node`.
+
+use napi::bindgen_prelude::{Buffer, Error, Result};
+use napi_derive::napi;
+
+use crate::{Generator, Ulid, UlidError};
+
+fn to_napi_error(e: UlidError) -> Error {
+    Error::from_reason(e.to_string())
+}
+
+/// checks that `seed_time` is a whole number of milliseconds that fits in 48
+/// bits, as the `ulid` package does
+fn seed_timestamp(seed_time: f64) -> Result<u64> {
+    if !seed_time.is_finite() || seed_time.fract() != 0.0 {
+        return Err(Error::from_reason("time must be an integer"));
+    }
+    if !(0.0..(1u64 << 48) as f64).contains(&seed_time) {
+        return Err(to_napi_error(UlidError::InvalidTimestamp));
+    }
+
+    Ok(seed_time as u64)
+}
+
+/// creates a new Ulid, in its canonical string form, for `seed_time` in
+/// milliseconds or the current time, failing for a `seed_time` that is not a
+/// whole number of milliseconds up to 2^48 - 1
+#[napi]
+pub fn ulid(seed_time: Option<f64>) -> Result<String> {
+    let timestamp = match seed_time {
+        Some(t) => seed_timestamp(t)?,
+        None => crate::clock::now_millis(),
+    };
+
+    Ok(Ulid::from_entropy(timestamp, crate::generator::os_entropy()).to_string())
+}
+
+/// returns the timestamp of a Ulid string in milliseconds
+#[napi]
+pub fn decode_time(id: String) -> Result<f64> {
+    // 48 bits are exactly representable in a double
+    Ulid::unmarshal(id)
+        .map(|ulid| ulid.timestamp() as f64)
+        .map_err(to_napi_error)
+}
+
+/// parses a Ulid string into its 16 bytes
+#[napi]
+pub fn parse(id: String) -> Result<Buffer> {
+    let ulid = Ulid::unmarshal(id).map_err(to_napi_error)?;
+    let bytes: [u8; 16] = ulid.into();
+    Ok(bytes.to_vec().into())
+}
+
+/// creates Ulids that are strictly increasing, including within the same
+/// millisecond
+#[napi]
+pub struct MonotonicFactory {
+    inner: Generator,
+}
+
+#[napi]
+impl MonotonicFactory {
+    /// creates new MonotonicFactory
+    #[napi(constructor)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> MonotonicFactory {
+        MonotonicFactory {
+            inner: Generator::new(),
+        }
+    }
+
+    /// creates the next Ulid, in its canonical string form, for `seed_time` in
+    /// milliseconds or the current time, failing for a `seed_time` that is not
+    /// a whole number of milliseconds up to 2^48 - 1
+    #[napi]
+    pub fn next(&mut self, seed_time: Option<f64>) -> Result<String> {
+        let ulid = match seed_time {
+            Some(t) => self
+                .inner
+                .create_from_entropy(seed_timestamp(t)?, crate::generator::os_entropy()),
+            None => self.inner.generate(),
+        };
+
+        ulid.map(|ulid| ulid.to_string()).map_err(to_napi_error)
+    }
+}
+
+/// creates a new MonotonicFactory
+#[napi]
+pub fn monotonic_factory() -> MonotonicFactory {
+    MonotonicFactory::new()
+}
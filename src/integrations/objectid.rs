@@ -0,0 +1,42 @@
+//! conversions from and to the 12 bytes of a MongoDB ObjectId, keeping their
+//! order
+//!
+//! The ObjectId second becomes the millisecond timestamp and its other 8 bytes
+//! the start of the entropy. The other way the timestamp is truncated to the
+//! second and the last 2 bytes of the entropy are dropped.
+
+use core::convert::TryFrom;
+
+use crate::{Ulid, UlidError};
+
+impl Ulid {
+    /// creates a Ulid from the 12 bytes of a MongoDB ObjectId
+    ///
+    /// The timestamp is the ObjectId's second in milliseconds, and the entropy
+    /// is its 5 random bytes and 3 byte counter followed by 2 zero bytes, so
+    /// the Ulids are in the same order as the ObjectIds.
+    pub fn from_object_id(object_id: &[u8; 12]) -> Ulid {
+        let seconds = u32::from_be_bytes([object_id[0], object_id[1], object_id[2], object_id[3]]);
+
+        let mut ans = Ulid([0; 16]);
+        ans.encode_time(u64::from(seconds) * 1000);
+        ans.0[6..14].copy_from_slice(&object_id[4..]);
+        ans
+    }
+
+    /// converts to the 12 bytes of a MongoDB ObjectId, the inverse of
+    /// `from_object_id`
+    ///
+    /// The timestamp is truncated to the second, and only the first 8 bytes of
+    /// the entropy are kept. Fails with `UlidError::InvalidObjectIdTimestamp`
+    /// for a timestamp after the last second of an ObjectId in 2106.
+    pub fn to_object_id(&self) -> Result<[u8; 12], UlidError> {
+        let seconds = u32::try_from(self.timestamp() / 1000)
+            .map_err(|_| UlidError::InvalidObjectIdTimestamp)?;
+
+        let mut ans = [0; 12];
+        ans[..4].copy_from_slice(&seconds.to_be_bytes());
+        ans[4..].copy_from_slice(&self.0[6..14]);
+        Ok(ans)
+    }
+}
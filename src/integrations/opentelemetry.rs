@@ -0,0 +1,39 @@
+//! OpenTelemetry support, converting between Ulids and `TraceId`s, so a request
+//! ID and a trace ID can be one value, shown in two forms
+//!
+//! Both are 128 bits, so the conversions keep every bit. A Ulid used as a trace
+//! ID has its randomness in the last 10 bytes, as W3C trace context asks of the
+//! last 7.
+
+use opentelemetry::trace::{TraceContextExt, TraceId};
+use opentelemetry::Context;
+
+use crate::Ulid;
+
+impl From<TraceId> for Ulid {
+    fn from(id: TraceId) -> Self {
+        Ulid(id.to_bytes())
+    }
+}
+
+impl From<Ulid> for TraceId {
+    fn from(u: Ulid) -> Self {
+        TraceId::from_bytes(u.0)
+    }
+}
+
+impl Ulid {
+    /// the trace ID of the active span as a Ulid, or `None` outside of a valid
+    /// span context
+    pub fn from_active_trace() -> Option<Ulid> {
+        let cx = Context::current();
+        let span = cx.span();
+        let span_context = span.span_context();
+
+        if span_context.is_valid() {
+            Some(span_context.trace_id().into())
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! `postgres-types` support, for tokio-postgres and postgres, mapping Ulids to
+//! the `uuid` and `bytea` types
+
+use std::convert::TryFrom;
+use std::error::Error;
+
+use bytes::BytesMut;
+use postgres_types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+use crate::{Id, Ulid};
+
+impl<'a> FromSql<'a> for Ulid {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(Ulid::try_from(raw)?)
+    }
+
+    accepts!(UUID, BYTEA);
+}
+
+impl ToSql for Ulid {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        out.extend_from_slice(&self.0);
+        Ok(IsNull::No)
+    }
+
+    accepts!(UUID, BYTEA);
+
+    to_sql_checked!();
+}
+
+impl<'a, T: ?Sized> FromSql<'a> for Id<T> {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ulid::from_sql(ty, raw).map(Id::from_ulid)
+    }
+
+    accepts!(UUID, BYTEA);
+}
+
+impl<T: ?Sized> ToSql for Id<T> {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        self.ulid().to_sql(ty, out)
+    }
+
+    accepts!(UUID, BYTEA);
+
+    to_sql_checked!();
+}
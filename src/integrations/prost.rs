@@ -0,0 +1,61 @@
+//! protobuf helpers for prost, carrying Ulids as `bytes` fields of 16 bytes
+//!
+//! `UlidMessage` is the message in `proto/ulid.proto`, for schemas that import
+//! it. For plain `bytes` fields in other messages, `decode` and
+//! `decode_optional` check the length, so services share one mapping and one
+//! error for malformed IDs.
+//!
+//! ```ignore
+//! let id = ulid_rs::prost::decode(&request.order_id)?;
+//! let reply = Reply { order_id: id.into(), ..Default::default() };
+//! ```
+
+use core::convert::TryFrom;
+
+use crate::{Ulid, UlidError};
+
+/// the `ulid.Ulid` message, holding the 16 bytes of a Ulid
+#[derive(Clone, PartialEq, Eq, Hash, prost::Message)]
+pub struct UlidMessage {
+    /// the 16 bytes, or none for no Ulid
+    #[prost(bytes = "vec", tag = "1")]
+    pub value: Vec<u8>,
+}
+
+impl From<Ulid> for UlidMessage {
+    fn from(u: Ulid) -> Self {
+        UlidMessage { value: u.into() }
+    }
+}
+
+impl TryFrom<&UlidMessage> for Ulid {
+    type Error = UlidError;
+
+    fn try_from(m: &UlidMessage) -> Result<Self, Self::Error> {
+        decode(&m.value)
+    }
+}
+
+impl TryFrom<UlidMessage> for Ulid {
+    type Error = UlidError;
+
+    fn try_from(m: UlidMessage) -> Result<Self, Self::Error> {
+        Ulid::try_from(&m)
+    }
+}
+
+/// decodes a required Ulid from a `bytes` field, failing with
+/// `UlidError::InvalidBufferLength` unless it holds 16 bytes
+pub fn decode(bytes: &[u8]) -> Result<Ulid, UlidError> {
+    Ulid::try_from(bytes)
+}
+
+/// decodes an optional Ulid from a `bytes` field, where the empty default of
+/// proto3 is `None`
+pub fn decode_optional(bytes: &[u8]) -> Result<Option<Ulid>, UlidError> {
+    if bytes.is_empty() {
+        Ok(None)
+    } else {
+        decode(bytes).map(Some)
+    }
+}
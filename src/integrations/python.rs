@@ -0,0 +1,81 @@
+//! Python bindings through PyO3
+//!
+//! The `ulid_rs` module exposes a single `Ulid` class, which is immutable,
+//! hashable and ordered like the Rust type, with `Ulid()`, `Ulid.parse()`,
+//! `Ulid.from_bytes()`, `timestamp`, `str()` and `bytes()`. Build with
+//! `cargo rustc --release --lib --features python,pyo3/extension-module --crate-type cdylib`
+//! and rename the library to `ulid_rs.so`, or use maturin.
+
+use std::borrow::Cow;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::{Ulid, UlidError};
+
+impl From<UlidError> for PyErr {
+    fn from(e: UlidError) -> PyErr {
+        PyValueError::new_err(e.to_string())
+    }
+}
+
+/// Python wrapper for Ulid
+#[pyclass(name = "Ulid", module = "ulid_rs", frozen, eq, ord, hash)]
+#[derive(Debug, PartialEq, PartialOrd, Hash)]
+pub(crate) struct PyUlid(Ulid);
+
+#[pymethods]
+impl PyUlid {
+    /// creates a new Ulid for `timestamp` in milliseconds or the current time,
+    /// with entropy from the operating system, raising ValueError for a
+    /// timestamp that does not fit in 48 bits
+    #[new]
+    #[pyo3(signature = (timestamp = None))]
+    pub(crate) fn new(timestamp: Option<u64>) -> PyResult<PyUlid> {
+        let timestamp = timestamp.unwrap_or_else(crate::clock::now_millis);
+        if timestamp >> 48 != 0 {
+            return Err(UlidError::InvalidTimestamp.into());
+        }
+
+        Ok(PyUlid(Ulid::from_entropy(
+            timestamp,
+            crate::generator::os_entropy(),
+        )))
+    }
+
+    /// parses the canonical string form, raising ValueError on invalid input
+    #[staticmethod]
+    pub(crate) fn parse(s: &str) -> PyResult<PyUlid> {
+        Ok(PyUlid(Ulid::unmarshal(s)?))
+    }
+
+    /// creates a Ulid from its 16 bytes
+    #[staticmethod]
+    pub(crate) fn from_bytes(b: [u8; 16]) -> PyUlid {
+        PyUlid(Ulid::from(b))
+    }
+
+    /// the timestamp in milliseconds
+    #[getter]
+    pub(crate) fn timestamp(&self) -> u64 {
+        self.0.timestamp()
+    }
+
+    pub(crate) fn __bytes__(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed((&self.0).into())
+    }
+
+    pub(crate) fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    pub(crate) fn __repr__(&self) -> String {
+        format!("Ulid('{}')", self.0)
+    }
+}
+
+/// Python module
+#[pymodule]
+fn ulid_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyUlid>()
+}
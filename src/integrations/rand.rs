@@ -0,0 +1,14 @@
+//! rand support, so `rng.gen::<Ulid>()` creates a Ulid for the current time
+//! with entropy from the rng
+
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use crate::{RngEntropy, Ulid};
+
+/// samples a Ulid for the current time, with entropy from the rng
+impl Distribution<Ulid> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Ulid {
+        Ulid::now(RngEntropy(rng))
+    }
+}
@@ -0,0 +1,49 @@
+//! parallel bulk generation with rayon
+//!
+//! Each task takes its entropy from the buffer of its worker thread, and the
+//! sorted variant also makes the Ulids unique.
+
+use rayon::prelude::*;
+
+use crate::Ulid;
+
+/// Ulids per task, each of which reads its own entropy
+const CHUNK: usize = 4096;
+
+impl Ulid {
+    /// creates `n` new Ulids like `Ulid::generate_batch`, split across the
+    /// threads of the rayon pool
+    ///
+    /// All of them share the same timestamp. As with `Ulid::generate`,
+    /// uniqueness rests on the 80 bits of entropy, which
+    /// `par_generate_batch_sorted` also checks.
+    ///
+    /// # Panics
+    ///
+    /// if the operating system fails to provide entropy
+    pub fn par_generate_batch(n: usize) -> Vec<Ulid> {
+        let timestamp = crate::clock::now_millis();
+
+        let mut ans = vec![Ulid([0; 16]); n];
+        ans.par_chunks_mut(CHUNK)
+            .for_each(|chunk| Ulid::generate_into_at(timestamp, chunk));
+        ans
+    }
+
+    /// creates `n` new, sorted and unique Ulids like `par_generate_batch`
+    ///
+    /// # Panics
+    ///
+    /// if the operating system fails to provide entropy
+    pub fn par_generate_batch_sorted(n: usize) -> Vec<Ulid> {
+        loop {
+            let mut ans = Ulid::par_generate_batch(n);
+            ans.par_sort_unstable();
+
+            // a repeat within 80 random bits is unlikely enough to start over
+            if !ans.par_windows(2).any(|w| w[0] == w[1]) {
+                return ans;
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! redis-rs support, storing Ulids as their 26 character string form, both as
+//! keys and as values
+
+use redis::{ErrorKind, FromRedisValue, RedisError, RedisResult, RedisWrite, ToRedisArgs, Value};
+
+use crate::{Id, Ulid};
+
+impl ToRedisArgs for Ulid {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg(&self.marshal())
+    }
+}
+
+impl FromRedisValue for Ulid {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        let s = match v {
+            Value::BulkString(bytes) => &bytes[..],
+            Value::SimpleString(s) => s.as_bytes(),
+            _ => {
+                return Err(RedisError::from((
+                    ErrorKind::TypeError,
+                    "Response was of incompatible type",
+                    format!("expected a ulid string (response was {:?})", v),
+                )))
+            }
+        };
+
+        Ulid::unmarshal(s).map_err(|e| {
+            RedisError::from((
+                ErrorKind::TypeError,
+                "Response was not a ulid",
+                e.to_string(),
+            ))
+        })
+    }
+}
+
+impl<T: ?Sized> ToRedisArgs for Id<T> {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        self.ulid().write_redis_args(out)
+    }
+}
+
+impl<T: ?Sized> FromRedisValue for Id<T> {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        Ulid::from_redis_value(v).map(Id::from_ulid)
+    }
+}
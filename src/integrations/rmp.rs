@@ -0,0 +1,115 @@
+//! MessagePack support for rmp-serde, storing Ulids as an extension type
+//!
+//! The `ext` serde helpers write a Ulid as a fixext 16 of type `EXT_TYPE`, 85,
+//! keeping it binary while other MessagePack readers can still tell it apart
+//! from other bytes.
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Call {
+//!     #[serde(with = "ulid_rs::rmp::ext")]
+//!     id: Ulid,
+//! }
+//! ```
+
+/// the MessagePack extension type for Ulids, 85 or an ASCII `U`
+pub const EXT_TYPE: i8 = 0x55;
+
+/// stores a Ulid as a fixext 16 of type `EXT_TYPE`
+pub mod ext {
+    use core::fmt;
+
+    use rmp_serde::MSGPACK_EXT_STRUCT_NAME;
+    use serde::de::{self, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::EXT_TYPE;
+    use crate::Ulid;
+
+    /// serializes as the extension type
+    pub fn serialize<S>(ulid: &Ulid, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(MSGPACK_EXT_STRUCT_NAME, &(EXT_TYPE, Payload(ulid.0)))
+    }
+
+    /// deserializes from the extension type, failing for other types
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Ulid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(MSGPACK_EXT_STRUCT_NAME, ExtVisitor)
+    }
+
+    /// the 16 bytes, as serde bytes rather than a sequence
+    struct Payload([u8; 16]);
+
+    impl Serialize for Payload {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.serialize_bytes(&self.0)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Payload {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_bytes(PayloadVisitor)
+        }
+    }
+
+    struct PayloadVisitor;
+
+    impl<'de> Visitor<'de> for PayloadVisitor {
+        type Value = Payload;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("16 bytes")
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<Payload, E>
+        where
+            E: de::Error,
+        {
+            let mut ans = [0; 16];
+
+            if v.len() != ans.len() {
+                return Err(E::invalid_length(v.len(), &self));
+            }
+
+            ans.copy_from_slice(v);
+            Ok(Payload(ans))
+        }
+    }
+
+    struct ExtVisitor;
+
+    impl<'de> Visitor<'de> for ExtVisitor {
+        type Value = Ulid;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "a msgpack extension of type {}", EXT_TYPE)
+        }
+
+        fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Ulid, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let (ext_type, payload) = <(i8, Payload)>::deserialize(deserializer)?;
+
+            if ext_type != EXT_TYPE {
+                return Err(de::Error::custom(format_args!(
+                    "unexpected msgpack extension type {} for a ulid",
+                    ext_type
+                )));
+            }
+
+            Ok(Ulid(payload.0))
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! rocket support, so routes like `/orders/<id>` and forms can take a Ulid
+//!
+//! `UlidError` responds with 400 Bad Request, to return from handlers taking
+//! `Result<Ulid, UlidError>`.
+
+use rocket::form::{self, FromFormField, ValueField};
+use rocket::http::impl_from_uri_param_identity;
+use rocket::http::uri::fmt::{Formatter, Part, UriDisplay};
+use rocket::http::Status;
+use rocket::request::{FromParam, Request};
+use rocket::response::{self, Responder};
+use std::fmt;
+
+use crate::{Ulid, UlidError};
+
+impl<'a> FromParam<'a> for Ulid {
+    type Error = UlidError;
+
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        Ulid::unmarshal(param)
+    }
+}
+
+impl<'v> FromFormField<'v> for Ulid {
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        Ulid::unmarshal(field.value).map_err(|e| {
+            form::Error::from(form::error::ErrorKind::Custom(
+                Status::BadRequest,
+                Box::new(e),
+            ))
+            .with_name(field.name)
+            .with_value(field.value)
+            .into()
+        })
+    }
+}
+
+impl<P: Part> UriDisplay<P> for Ulid {
+    fn fmt(&self, f: &mut Formatter<'_, P>) -> fmt::Result {
+        // the crockford alphabet needs no percent-encoding
+        f.write_raw(self.to_string())
+    }
+}
+
+impl_from_uri_param_identity!(Ulid);
+
+/// responds with 400 Bad Request and the error message, for handlers taking a
+/// `Result<Ulid, UlidError>` parameter
+impl<'r> Responder<'r, 'static> for UlidError {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        (Status::BadRequest, self.to_string()).respond_to(request)
+    }
+}
@@ -0,0 +1,81 @@
+//! rusqlite support, storing Ulids as a 16 byte `BLOB`, or as 26 character
+//! `TEXT` through `UlidText`
+//!
+//! Both types read either form.
+
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, Value, ValueRef};
+
+use crate::{Id, Ulid};
+
+/// a Ulid stored in SQLite as its 26 character string form, in place of the
+/// 16 byte blob used for `Ulid`
+///
+/// Both forms can be read into either type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UlidText(pub Ulid);
+
+impl ToSql for Ulid {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Borrowed(ValueRef::Blob(&self.0)))
+    }
+}
+
+impl FromSql for Ulid {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Blob(blob) => {
+                let mut ans = [0; 16];
+
+                if blob.len() != ans.len() {
+                    return Err(FromSqlError::InvalidBlobSize {
+                        expected_size: ans.len(),
+                        blob_size: blob.len(),
+                    });
+                }
+
+                ans.copy_from_slice(blob);
+                Ok(Ulid(ans))
+            }
+            ValueRef::Text(text) => {
+                Ulid::unmarshal(text).map_err(|e| FromSqlError::Other(Box::new(e)))
+            }
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+impl<T: ?Sized> ToSql for Id<T> {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Borrowed(ValueRef::Blob(&(**self).0)))
+    }
+}
+
+impl<T: ?Sized> FromSql for Id<T> {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        Ulid::column_result(value).map(Id::from_ulid)
+    }
+}
+
+impl ToSql for UlidText {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Owned(Value::Text(self.0.to_string())))
+    }
+}
+
+impl FromSql for UlidText {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        Ulid::column_result(value).map(UlidText)
+    }
+}
+
+impl From<Ulid> for UlidText {
+    fn from(u: Ulid) -> Self {
+        UlidText(u)
+    }
+}
+
+impl From<UlidText> for Ulid {
+    fn from(u: UlidText) -> Self {
+        u.0
+    }
+}
@@ -0,0 +1,24 @@
+//! schemars support, describing a Ulid in JSON Schema as its 26 character
+//! Crockford base32 string
+
+use super::PATTERN;
+use crate::Ulid;
+
+impl schemars::JsonSchema for Ulid {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Ulid".into()
+    }
+
+    fn schema_id() -> std::borrow::Cow<'static, str> {
+        concat!(module_path!(), "::Ulid").into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": "string",
+            "minLength": 26,
+            "maxLength": 26,
+            "pattern": PATTERN,
+        })
+    }
+}
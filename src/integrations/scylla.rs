@@ -0,0 +1,87 @@
+//! Scylla and Cassandra support through the scylla driver, binding Ulids to
+//! `uuid` and `blob` columns
+
+use std::convert::TryFrom;
+
+use scylla::cluster::metadata::{ColumnType, NativeType};
+use scylla::deserialize::value::DeserializeValue;
+use scylla::deserialize::{DeserializationError, FrameSlice, TypeCheckError};
+use scylla::serialize::value::SerializeValue;
+use scylla::serialize::writers::{CellWriter, WrittenCellProof};
+use scylla::serialize::SerializationError;
+
+use crate::{Id, Ulid};
+
+/// the CQL types a Ulid is bound to and read from
+const TYPES: &[ColumnType<'static>] = &[
+    ColumnType::Native(NativeType::Uuid),
+    ColumnType::Native(NativeType::Blob),
+];
+
+impl SerializeValue for Ulid {
+    fn serialize<'b>(
+        &self,
+        typ: &ColumnType,
+        writer: CellWriter<'b>,
+    ) -> Result<WrittenCellProof<'b>, SerializationError> {
+        use scylla::serialize::value::{BuiltinTypeCheckError, BuiltinTypeCheckErrorKind};
+
+        match typ {
+            ColumnType::Native(NativeType::Uuid) => {
+                uuid::Uuid::from_bytes(self.0).serialize(typ, writer)
+            }
+            ColumnType::Native(NativeType::Blob) => (&self.0[..]).serialize(typ, writer),
+            _ => Err(SerializationError::new(BuiltinTypeCheckError {
+                rust_name: core::any::type_name::<Ulid>(),
+                got: typ.clone().into_owned(),
+                kind: BuiltinTypeCheckErrorKind::MismatchedType { expected: TYPES },
+            })),
+        }
+    }
+}
+
+impl<'frame, 'metadata> DeserializeValue<'frame, 'metadata> for Ulid {
+    fn type_check(typ: &ColumnType) -> Result<(), TypeCheckError> {
+        use scylla::deserialize::value::{BuiltinTypeCheckError, BuiltinTypeCheckErrorKind};
+
+        match typ {
+            ColumnType::Native(NativeType::Uuid) | ColumnType::Native(NativeType::Blob) => Ok(()),
+            _ => Err(TypeCheckError::new(BuiltinTypeCheckError {
+                rust_name: core::any::type_name::<Ulid>(),
+                cql_type: typ.clone().into_owned(),
+                kind: BuiltinTypeCheckErrorKind::MismatchedType { expected: TYPES },
+            })),
+        }
+    }
+
+    fn deserialize(
+        typ: &'metadata ColumnType<'metadata>,
+        v: Option<FrameSlice<'frame>>,
+    ) -> Result<Self, DeserializationError> {
+        let bytes = <&[u8] as DeserializeValue>::deserialize(typ, v)?;
+        Ulid::try_from(bytes).map_err(DeserializationError::new)
+    }
+}
+
+impl<T: ?Sized> SerializeValue for Id<T> {
+    fn serialize<'b>(
+        &self,
+        typ: &ColumnType,
+        writer: CellWriter<'b>,
+    ) -> Result<WrittenCellProof<'b>, SerializationError> {
+        self.ulid().serialize(typ, writer)
+    }
+}
+
+impl<'frame, 'metadata, T: ?Sized> DeserializeValue<'frame, 'metadata> for Id<T> {
+    fn type_check(typ: &ColumnType) -> Result<(), TypeCheckError> {
+        <Ulid as DeserializeValue>::type_check(typ)
+    }
+
+    fn deserialize(
+        typ: &'metadata ColumnType<'metadata>,
+        v: Option<FrameSlice<'frame>>,
+    ) -> Result<Self, DeserializationError> {
+        Ulid::deserialize(typ, v).map(Id::from_ulid)
+    }
+}
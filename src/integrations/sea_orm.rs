@@ -0,0 +1,120 @@
+//! SeaORM support, storing Ulids as the 16 raw bytes in a `binary(16)` column,
+//! so a Ulid can be the primary key of an entity
+
+use core::convert::TryFrom;
+
+use sea_orm::sea_query::{ArrayType, ColumnType, Nullable, Value, ValueType, ValueTypeErr};
+use sea_orm::{
+    ActiveValue, ColIdx, DbErr, IntoActiveValue, QueryResult, TryFromU64, TryGetError, TryGetable,
+};
+
+use crate::{Id, Ulid};
+
+impl From<Ulid> for Value {
+    fn from(u: Ulid) -> Self {
+        Value::Bytes(Some(Box::new(u.0.to_vec())))
+    }
+}
+
+impl Nullable for Ulid {
+    fn null() -> Value {
+        Value::Bytes(None)
+    }
+}
+
+impl ValueType for Ulid {
+    fn try_from(v: Value) -> Result<Self, ValueTypeErr> {
+        match v {
+            Value::Bytes(Some(bytes)) => {
+                <Ulid as TryFrom<&[u8]>>::try_from(&bytes[..]).map_err(|_| ValueTypeErr)
+            }
+            _ => Err(ValueTypeErr),
+        }
+    }
+
+    fn type_name() -> String {
+        "Ulid".to_string()
+    }
+
+    fn array_type() -> ArrayType {
+        ArrayType::Bytes
+    }
+
+    fn column_type() -> ColumnType {
+        ColumnType::Binary(16)
+    }
+}
+
+impl TryGetable for Ulid {
+    fn try_get_by<I: ColIdx>(res: &QueryResult, index: I) -> Result<Self, TryGetError> {
+        let bytes = <Vec<u8> as TryGetable>::try_get_by(res, index)?;
+
+        <Ulid as TryFrom<&[u8]>>::try_from(&bytes[..]).map_err(|e| {
+            TryGetError::DbErr(DbErr::TryIntoErr {
+                from: "Vec<u8>",
+                into: "Ulid",
+                source: Box::new(e),
+            })
+        })
+    }
+}
+
+impl TryFromU64 for Ulid {
+    fn try_from_u64(_: u64) -> Result<Self, DbErr> {
+        Err(DbErr::ConvertFromU64("Ulid"))
+    }
+}
+
+impl IntoActiveValue<Ulid> for Ulid {
+    fn into_active_value(self) -> ActiveValue<Ulid> {
+        ActiveValue::Set(self)
+    }
+}
+
+impl<T: ?Sized> From<Id<T>> for Value {
+    fn from(id: Id<T>) -> Self {
+        id.ulid().into()
+    }
+}
+
+impl<T: ?Sized> Nullable for Id<T> {
+    fn null() -> Value {
+        Ulid::null()
+    }
+}
+
+impl<T: ?Sized> ValueType for Id<T> {
+    fn try_from(v: Value) -> Result<Self, ValueTypeErr> {
+        <Ulid as ValueType>::try_from(v).map(Id::from_ulid)
+    }
+
+    fn type_name() -> String {
+        "Id".to_string()
+    }
+
+    fn array_type() -> ArrayType {
+        Ulid::array_type()
+    }
+
+    fn column_type() -> ColumnType {
+        Ulid::column_type()
+    }
+}
+
+impl<T: ?Sized> TryGetable for Id<T> {
+    fn try_get_by<I: ColIdx>(res: &QueryResult, index: I) -> Result<Self, TryGetError> {
+        Ulid::try_get_by(res, index).map(Id::from_ulid)
+    }
+}
+
+impl<T: ?Sized> TryFromU64 for Id<T> {
+    fn try_from_u64(_: u64) -> Result<Self, DbErr> {
+        Err(DbErr::ConvertFromU64("Id"))
+    }
+}
+
+impl<T: ?Sized> IntoActiveValue<Id<T>> for Id<T> {
+    fn into_active_value(self) -> ActiveValue<Id<T>> {
+        ActiveValue::Set(self)
+    }
+}
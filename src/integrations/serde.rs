@@ -0,0 +1,84 @@
+//! serde support, as the 26 character string form in human readable formats
+//! and as the 16 bytes in the others
+//!
+//! Both forms are accepted when deserializing from any format.
+
+use core::convert::TryFrom;
+use core::fmt;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Id, Ulid};
+
+impl Serialize for Ulid {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_bytes(&self.0)
+        }
+    }
+}
+
+/// accepts the string form, or the 16 bytes
+struct UlidVisitor;
+
+impl<'de> Visitor<'de> for UlidVisitor {
+    type Value = Ulid;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a ulid string or 16 bytes")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Ulid, E>
+    where
+        E: de::Error,
+    {
+        Ulid::unmarshal(v).map_err(E::custom)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Ulid, E>
+    where
+        E: de::Error,
+    {
+        match v.len() {
+            26 => Ulid::unmarshal(v).map_err(E::custom),
+            _ => Ulid::try_from(v).map_err(E::custom),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Ulid {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(UlidVisitor)
+        } else {
+            deserializer.deserialize_bytes(UlidVisitor)
+        }
+    }
+}
+
+impl<T: ?Sized> Serialize for Id<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.ulid().serialize(serializer)
+    }
+}
+
+impl<'de, T: ?Sized> Deserialize<'de> for Id<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ulid::deserialize(deserializer).map(Id::from_ulid)
+    }
+}
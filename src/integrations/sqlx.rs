@@ -0,0 +1,212 @@
+//! sqlx support for Postgres, MySQL and SQLite
+//!
+//! Ulids are bound as a Postgres `uuid`, or as a `bytea` through `UlidBytea`,
+//! and as a blob of the 16 bytes on MySQL and SQLite. They are read from a
+//! Postgres `uuid` or `bytea`, a MySQL `BINARY(16)` or an SQLite `BLOB`, as well
+//! as from the 26 character string form in a MySQL or SQLite text column.
+
+use core::convert::TryFrom;
+
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::mysql::{MySql, MySqlTypeInfo, MySqlValueRef};
+use sqlx::postgres::types::Oid;
+use sqlx::postgres::{
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
+use sqlx::sqlite::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+use sqlx::{Database, Decode, Encode, Type};
+
+use crate::{Id, Ulid, UlidError};
+
+/// the oids of the built-in `uuid` and `uuid[]` types
+const UUID: Oid = Oid(2950);
+const UUID_ARRAY: Oid = Oid(2951);
+
+/// a Ulid bound to Postgres as a `bytea` of its 16 bytes, in place of the
+/// `uuid` used for `Ulid`
+///
+/// Both column types can be read into either type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UlidBytea(pub Ulid);
+
+/// decodes the 16 bytes, or the string form from a text column
+pub(crate) fn decode_bytes(bytes: &[u8]) -> Result<Ulid, UlidError> {
+    match <[u8; 16]>::try_from(bytes) {
+        Ok(bytes) => Ok(Ulid(bytes)),
+        Err(_) => Ulid::unmarshal(bytes),
+    }
+}
+
+impl Type<Postgres> for Ulid {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_oid(UUID)
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::with_oid(UUID) || <Vec<u8> as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl PgHasArrayType for Ulid {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_oid(UUID_ARRAY)
+    }
+}
+
+impl Encode<'_, Postgres> for Ulid {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        buf.extend_from_slice(&self.0);
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Postgres> for Ulid {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        let ans = match value.format() {
+            PgValueFormat::Binary => decode_bytes(value.as_bytes()?),
+            PgValueFormat::Text => {
+                let s = value.as_str()?;
+
+                // bytea is sent as `\x` followed by hex digits
+                match s.strip_prefix("\\x") {
+                    Some(hex) => Ulid::from_hex(hex),
+                    None => Ulid::parse_uuid(s),
+                }
+            }
+        };
+
+        Ok(ans?)
+    }
+}
+
+impl Type<Postgres> for UlidBytea {
+    fn type_info() -> PgTypeInfo {
+        <Vec<u8> as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <Ulid as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl PgHasArrayType for UlidBytea {
+    fn array_type_info() -> PgTypeInfo {
+        <Vec<u8> as PgHasArrayType>::array_type_info()
+    }
+}
+
+impl Encode<'_, Postgres> for UlidBytea {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        <Ulid as Encode<Postgres>>::encode_by_ref(&self.0, buf)
+    }
+}
+
+impl Decode<'_, Postgres> for UlidBytea {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        <Ulid as Decode<Postgres>>::decode(value).map(UlidBytea)
+    }
+}
+
+impl From<Ulid> for UlidBytea {
+    fn from(u: Ulid) -> Self {
+        UlidBytea(u)
+    }
+}
+
+impl From<UlidBytea> for Ulid {
+    fn from(u: UlidBytea) -> Self {
+        u.0
+    }
+}
+
+impl Type<MySql> for Ulid {
+    fn type_info() -> MySqlTypeInfo {
+        <[u8] as Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        <[u8] as Type<MySql>>::compatible(ty)
+    }
+}
+
+impl Encode<'_, MySql> for Ulid {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+        <&[u8] as Encode<MySql>>::encode(&self.0, buf)
+    }
+}
+
+impl Decode<'_, MySql> for Ulid {
+    fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        Ok(decode_bytes(<&[u8] as Decode<MySql>>::decode(value)?)?)
+    }
+}
+
+impl Type<Sqlite> for Ulid {
+    fn type_info() -> SqliteTypeInfo {
+        <[u8] as Type<Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <[u8] as Type<Sqlite>>::compatible(ty)
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for Ulid {
+    fn encode_by_ref(
+        &self,
+        args: &mut Vec<SqliteArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        args.push(SqliteArgumentValue::Blob(self.0.to_vec().into()));
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Sqlite> for Ulid {
+    fn decode(value: SqliteValueRef<'_>) -> Result<Self, BoxDynError> {
+        Ok(decode_bytes(<&[u8] as Decode<Sqlite>>::decode(value)?)?)
+    }
+}
+
+impl<T: ?Sized, DB> Type<DB> for Id<T>
+where
+    DB: Database,
+    Ulid: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <Ulid as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <Ulid as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<T: ?Sized> PgHasArrayType for Id<T> {
+    fn array_type_info() -> PgTypeInfo {
+        Ulid::array_type_info()
+    }
+}
+
+impl<'q, T: ?Sized, DB> Encode<'q, DB> for Id<T>
+where
+    DB: Database,
+    Ulid: Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        self.ulid().encode_by_ref(buf)
+    }
+}
+
+impl<'r, T: ?Sized, DB> Decode<'r, DB> for Id<T>
+where
+    DB: Database,
+    Ulid: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ulid::decode(value).map(Id::from_ulid)
+    }
+}
@@ -0,0 +1,65 @@
+//! a tower-http `MakeRequestId` using Ulids as request IDs, for axum, hyper
+//! and other tower based services
+//!
+//! `MakeUlidRequestId` plugs into `tower_http::request_id`: `SetRequestIdLayer`
+//! gives requests without an `x-request-id` header a new, strictly increasing
+//! Ulid in it, and `PropagateRequestIdLayer` copies it onto the response.
+//!
+//! ```ignore
+//! use tower_http::request_id::{PropagateRequestIdLayer, SetRequestIdLayer};
+//!
+//! let app = Router::new()
+//!     .route("/", get(handler))
+//!     .layer(PropagateRequestIdLayer::x_request_id())
+//!     .layer(SetRequestIdLayer::x_request_id(MakeUlidRequestId::new()));
+//! ```
+
+use std::sync::{Arc, Mutex};
+
+use http::{HeaderValue, Request};
+use tower_http::request_id::{MakeRequestId, RequestId};
+
+use crate::{Generator, Ulid};
+
+/// a `MakeRequestId` making each request ID a new Ulid
+///
+/// The Ulids come from one `Generator` shared by clones, so they increase
+/// strictly in the order requests arrive.
+#[derive(Clone, Debug)]
+pub struct MakeUlidRequestId {
+    generator: Arc<Mutex<Generator>>,
+}
+
+impl MakeUlidRequestId {
+    /// creates new MakeUlidRequestId
+    pub fn new() -> MakeUlidRequestId {
+        MakeUlidRequestId {
+            generator: Arc::new(Mutex::new(Generator::new())),
+        }
+    }
+
+    /// the next Ulid from the shared Generator, or a random one in the
+    /// unlikely case its entropy overflows within a millisecond
+    fn next(&self) -> Ulid {
+        let mut generator = match self.generator.lock() {
+            Ok(generator) => generator,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        generator.generate().unwrap_or_else(|_| Ulid::generate())
+    }
+}
+
+impl Default for MakeUlidRequestId {
+    fn default() -> Self {
+        MakeUlidRequestId::new()
+    }
+}
+
+impl MakeRequestId for MakeUlidRequestId {
+    fn make_request_id<B>(&mut self, _: &Request<B>) -> Option<RequestId> {
+        // the string form is always a valid header value
+        let value = HeaderValue::from_bytes(&self.next().marshal()).unwrap();
+        Some(RequestId::new(value))
+    }
+}
@@ -0,0 +1,23 @@
+//! utoipa support, with the same string schema as schemars, for OpenAPI
+//! documents generated by utoipa
+
+use super::PATTERN;
+use crate::Ulid;
+
+impl utoipa::PartialSchema for Ulid {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        utoipa::openapi::ObjectBuilder::new()
+            .schema_type(utoipa::openapi::schema::Type::String)
+            .min_length(Some(26))
+            .max_length(Some(26))
+            .pattern(Some(PATTERN))
+            .description(Some("ULID in its canonical Crockford base32 form"))
+            .into()
+    }
+}
+
+impl utoipa::ToSchema for Ulid {
+    fn name() -> std::borrow::Cow<'static, str> {
+        "Ulid".into()
+    }
+}
@@ -0,0 +1,53 @@
+//! conversions from and to `uuid::Uuid`, keeping the 16 bytes as they are,
+//! and to and from a UUIDv7 with the same timestamp
+
+use crate::{Ulid, UlidError};
+
+impl From<uuid::Uuid> for Ulid {
+    fn from(u: uuid::Uuid) -> Self {
+        Ulid(u.into_bytes())
+    }
+}
+
+impl From<Ulid> for uuid::Uuid {
+    fn from(u: Ulid) -> Self {
+        uuid::Uuid::from_bytes(u.0)
+    }
+}
+
+impl Ulid {
+    /// converts to a UUIDv7 with the same timestamp
+    ///
+    /// A UUIDv7 has 74 random bits against the 80 of a Ulid, so the 6 most
+    /// significant bits of the entropy are dropped, and the version and
+    /// variant bits are set. Keeping the low bits means Ulids incremented
+    /// within the same millisecond map to distinct UUIDs.
+    pub fn to_uuid_v7(&self) -> uuid::Uuid {
+        let val = u128::from_be_bytes(self.0);
+
+        let timestamp = val >> 80;
+        let rand_a = (val >> 62) & 0xFFF;
+        let rand_b = val & ((1 << 62) - 1);
+
+        let ans = (timestamp << 80) | (0x7 << 76) | (rand_a << 64) | (0b10 << 62) | rand_b;
+        uuid::Uuid::from_u128(ans)
+    }
+
+    /// creates a Ulid from a UUIDv7, the inverse of `to_uuid_v7`
+    ///
+    /// The 6 most significant bits of the entropy are set to zero.
+    pub fn from_uuid_v7(u: uuid::Uuid) -> Result<Ulid, UlidError> {
+        if u.get_version_num() != 7 || u.get_variant() != uuid::Variant::RFC4122 {
+            return Err(UlidError::InvalidUuidVersion);
+        }
+
+        let val = u.as_u128();
+
+        let timestamp = val >> 80;
+        let rand_a = (val >> 64) & 0xFFF;
+        let rand_b = val & ((1 << 62) - 1);
+
+        let ans = (timestamp << 80) | (rand_a << 62) | rand_b;
+        Ok(Ulid(ans.to_be_bytes()))
+    }
+}
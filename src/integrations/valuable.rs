@@ -0,0 +1,38 @@
+//! valuable support, so tracing records Ulids as structured values with
+//! `tracing::field::valuable(&ulid)`
+//!
+//! A Ulid is a tuple struct named `Ulid` with one field, its string form, which
+//! is written on the stack while visiting. tracing's own `Value` is sealed, and
+//! `%ulid` records the string form through `Display`.
+
+use valuable::{Fields, StructDef, Structable, Valuable, Value, Visit};
+
+use crate::{Id, Ulid};
+
+impl Valuable for Ulid {
+    fn as_value(&self) -> Value<'_> {
+        Value::Structable(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        // marshal only ever produces ASCII
+        let s = self.marshal();
+        visit.visit_unnamed_fields(&[Value::String(core::str::from_utf8(&s).unwrap())]);
+    }
+}
+
+impl Structable for Ulid {
+    fn definition(&self) -> StructDef<'_> {
+        StructDef::new_static("Ulid", Fields::Unnamed(1))
+    }
+}
+
+impl<T: ?Sized> Valuable for Id<T> {
+    fn as_value(&self) -> Value<'_> {
+        Value::Structable(&**self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        self.ulid().visit(visit)
+    }
+}
@@ -0,0 +1,46 @@
+//! JavaScript bindings through wasm-bindgen
+//!
+//! Entropy comes from `crypto.getRandomValues` and time from `Date.now`, both
+//! looked up on the global object, so the bindings work in browsers, web
+//! workers, Cloudflare Workers and Node.js 19 or later.
+//!
+//! Exports `new_ulid`, `parse` and `timestamp_ms`, and makes `Ulid::now` read
+//! the time from `Date.now` on `wasm32-unknown-unknown`. Build the module with
+//! `cargo rustc --release --lib --target wasm32-unknown-unknown --features wasm --crate-type cdylib`
+//! and run `wasm-bindgen` on the output.
+
+use wasm_bindgen::prelude::*;
+
+use crate::Ulid;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = crypto, js_name = getRandomValues)]
+    fn get_random_values(buf: &mut [u8]);
+
+    #[wasm_bindgen(js_namespace = Date, js_name = now)]
+    pub(crate) fn date_now() -> f64;
+}
+
+/// creates a new Ulid for the current time, in its canonical string form
+#[wasm_bindgen]
+pub fn new_ulid() -> String {
+    let mut entropy = [0; 10];
+    get_random_values(&mut entropy);
+
+    Ulid::from_entropy(crate::clock::now_millis(), entropy).to_string()
+}
+
+/// parses a Ulid string into its 16 bytes, throwing on invalid input
+#[wasm_bindgen]
+pub fn parse(s: &str) -> Result<Vec<u8>, JsError> {
+    Ok(Ulid::unmarshal(s)?.into())
+}
+
+/// returns the timestamp of a Ulid string in milliseconds, throwing on
+/// invalid input
+#[wasm_bindgen]
+pub fn timestamp_ms(s: &str) -> Result<f64, JsError> {
+    // 48 bits are exactly representable in an f64
+    Ok(Ulid::unmarshal(s)?.timestamp() as f64)
+}
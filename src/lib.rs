@@ -7,210 +7,273 @@
 //! ```
 //!
 //! Takes the last 48 bits of the passed timestamp and calls the passed closure
-//! 10 times for a random value.
+//! 10 times for a random value. With `std`, `Ulid::now(Fn() -> u8)` does the same
+//! with the current time in milliseconds.
 //!
-//! In place of explicit MarshalBinary and UnmarshalBinary, implements
-//! `Into<[u8; 16]>`, `Into<&[u8]>`, `Into<Vec<u8>>`, `From<[u8; 16]>` and `TryFrom<&[u8]>`
+//! Beyond that
 //!
-//! Along with `marshal` that returns 26 UTF-8 words, `TryInto<String>`, `TryInto<&str>`
-//! and `ToString` are also implemented.
+//! - `Generator` creates strictly increasing Ulids, optionally with a node ID or
+//!   lane in the entropy (`ShardedGenerator`), an `OverflowPolicy`, counters in
+//!   `GeneratorStats`, and with `std` persisted by `PersistentGenerator`
+//! - `Clock` and `EntropySource` supply the time and the entropy, with
+//!   `EpochClock` for custom epochs and `BufferedEntropy` for reading in chunks
+//! - `Ulid64`, `NonNilUlid` and `Id<T>` are a compact variant, a Ulid with a niche
+//!   for `Option`, and a Ulid tagged with its entity type
+//! - `marshal` and `unmarshal` encode and decode the 26 character form, with SIMD
+//!   on x86 and aarch64, along with `unmarshal_with` and a `Strictness`,
+//!   `marshal_checked` with a check symbol, `parse_many`, `write_to`,
+//!   `display_redacted` and the timestamp prefix helpers
+//! - `UlidRange`, `min_for_timestamp` and `max_for_timestamp` bound range scans,
+//!   and `Duration` arithmetic, `duration_since` and `age` work with the timestamp
+//! - with `std`, `UlidWriter` and `UlidReader` write and read the 16 bytes over
+//!   `io::Write` and `io::Read`
 //!
-//! Along with `unmarshal` that works with `AsRef<[u8]>`, `TryFrom<String>` and `TryFrom<&str>`
-//! are also implemented.
+//! The crate is `no_std` when the default `std` feature is disabled. Default
+//! features are kept to `std`, and every integration is an opt-in, additive
+//! cargo feature that only adds trait implementations or methods. Encoding
+//! lives in `codec`, creation in `generator`, and each integration in its own
+//! module under `integrations`, compiled only when its feature is enabled and
+//! documented there.
+//!
+//! Available features
+//!
+//! | feature | adds |
+//! | --- | --- |
+//! | `std` (default) | `std::error::Error` for `UlidError`, and the APIs that need the clock or `io`. Implies `alloc` |
+//! | `alloc` | `String` and `Vec` conversions, and the encoders returning `String` |
+//! | `getrandom` | `Ulid::generate`, `generate_batch`, `generate_into`, `generate_local` and `UlidIter`, with entropy from the operating system |
+//! | `atomic` | `AtomicGenerator`, shared through `&self` with a 128 bit compare and swap |
+//! | `tokio` | `AsyncGenerator`, which sleeps until the next millisecond on overflow |
+//! | `pool` | `UlidPool`, a ring buffer of Ulids kept full by a background thread |
+//! | `testing` | the `testing` module, with `MockClock` and `MockGenerator` |
+//! | `rand_core` | `RngEntropy`, an `EntropySource` from any `rand_core::RngCore` |
+//! | `seeded` | `SeededGenerator`, with reproducible entropy from a seeded ChaCha20 rng |
+//! | `rand` | `Distribution<Ulid>` for rand's `Standard` |
+//! | `fake` | `Dummy<Faker>` from fake |
+//! | `cipher` | `UlidCipher`, encrypting Ulids into Ulids with AES-128 |
+//! | `signed` | `UlidSigner`, appending and verifying an HMAC-SHA256 tag |
+//! | `derive` | `#[derive(UlidId)]` for newtypes around a Ulid |
+//! | `nightly` | `core::iter::Step`, so `start..end` of Ulids iterates |
+//! | `serde` | `Serialize` and `Deserialize`, as a string or the 16 bytes |
+//! | `borsh`, `rkyv`, `bytemuck`, `zerocopy` | binary encodings and zero copy casts of the 16 bytes |
+//! | `schemars`, `utoipa` | JSON Schema and OpenAPI schemas of the string form |
+//! | `uuid`, `ksuid`, `objectid` | conversions from and to UUIDs, KSUIDs and MongoDB ObjectIds |
+//! | `sqlx`, `diesel`, `postgres`, `rusqlite`, `sea-orm`, `scylla`, `redis` | database column types |
+//! | `bson`, `rmp`, `ciborium`, `prost`, `flatbuffers`, `arrow` | serialization formats and their helpers |
+//! | `async-graphql`, `juniper` | a `Ulid` GraphQL scalar |
+//! | `rocket`, `clap`, `tower` | route and form parameters, command line arguments and request IDs |
+//! | `valuable`, `opentelemetry` | structured tracing values and conversions to `TraceId` |
+//! | `rayon` | `Ulid::par_generate_batch` and `par_generate_batch_sorted` |
+//! | `embedded`, `embedded-hal` | `heapless` and `defmt` support, and `HalRngEntropy` from a hardware RNG |
+//! | `wasm`, `ffi`, `napi`, `python`, `uniffi` | bindings for JavaScript, C, Node.js, Python, Kotlin and Swift |
+//! | `cli` | the `ulid` binary, with `generate`, `inspect`, `validate`, `convert`, `sort` and `ts` |
 //!
 //! Most benchmarks line up with similar performance from C++, with some showing
 //! improvements. Benchmarks are run on GitHub actions using criterion.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "nightly", feature(step_trait))]
 #![deny(missing_docs)]
 
-use std::convert::TryFrom;
-use std::convert::TryInto;
-
-use thiserror::Error;
-
-#[cfg(test)]
+#[cfg(feature = "alloc")]
+extern crate alloc;
+// lets the tests use `#[derive(UlidId)]`, which expands to `::ulid_rs` paths
+#[cfg(all(test, feature = "derive"))]
+extern crate self as ulid_rs;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::convert::TryInto;
+
+#[cfg(feature = "cipher")]
+mod cipher;
+mod clock;
+mod codec;
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod derive;
+mod entropy;
+mod error;
+mod generator;
+mod id;
+mod integrations;
+mod non_nil;
+#[cfg(feature = "std")]
+mod persist;
+#[cfg(feature = "pool")]
+mod pool;
+mod range;
+#[cfg(feature = "signed")]
+mod signed;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod time;
+mod ulid64;
+
+#[cfg(feature = "cipher")]
+pub use cipher::UlidCipher;
+pub use clock::{Clock, EpochClock, SystemClock};
+pub use codec::{ParseMany, Redacted, Strictness};
+#[cfg(feature = "alloc")]
+pub use entropy::BufferedEntropy;
+pub use entropy::EntropySource;
+#[cfg(feature = "embedded-hal")]
+pub use entropy::HalRngEntropy;
+#[cfg(feature = "rand_core")]
+pub use entropy::RngEntropy;
+pub use error::UlidError;
+#[cfg(feature = "tokio")]
+pub use generator::AsyncGenerator;
+#[cfg(feature = "atomic")]
+pub use generator::AtomicGenerator;
+pub use generator::OverflowPolicy;
+#[cfg(feature = "seeded")]
+pub use generator::SeededGenerator;
+#[cfg(all(feature = "std", feature = "getrandom"))]
+pub use generator::UlidIter;
+pub use generator::{Generator, GeneratorStats};
+#[cfg(feature = "std")]
+pub use generator::{ShardedGenerator, ShardedLane};
+pub use id::Id;
+#[cfg(feature = "arrow")]
+pub use integrations::arrow;
+#[cfg(feature = "bson")]
+pub use integrations::bson;
+#[cfg(feature = "ciborium")]
+pub use integrations::ciborium;
+#[cfg(feature = "clap")]
+pub use integrations::clap::UlidValueParser;
+#[cfg(feature = "prost")]
+pub use integrations::prost;
+#[cfg(feature = "rmp")]
+pub use integrations::rmp;
+#[cfg(feature = "rusqlite")]
+pub use integrations::rusqlite::UlidText;
+#[cfg(feature = "sqlx")]
+pub use integrations::sqlx::UlidBytea;
+#[cfg(feature = "tower")]
+pub use integrations::tower::MakeUlidRequestId;
+pub use non_nil::NonNilUlid;
+#[cfg(feature = "std")]
+pub use persist::{FileStore, GeneratorStore, PersistentGenerator};
+#[cfg(feature = "pool")]
+pub use pool::UlidPool;
+pub use range::UlidRange;
+#[cfg(feature = "signed")]
+pub use signed::{SignedUlid, UlidSigner};
+#[cfg(feature = "std")]
+pub use stream::{UlidReader, UlidWriter};
+pub use ulid64::Ulid64;
+#[cfg(feature = "derive")]
+pub use ulid_rs_derive::UlidId;
+
+#[cfg(all(test, feature = "std"))]
 mod tests;
 
-/// Crockford's base32
-static ENCODING: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
-
-/// stores decimal encodings for characters.
-static DECODING: &[u8; 256] = &[
-    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
-    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
-    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
-    /* 0     1     2     3     4     5     6     7  */
-    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
-    /* 8     9                                      */
-    0x08, 0x09, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
-    /*    10(A) 11(B) 12(C) 13(D) 14(E) 15(F) 16(G) */
-    0xFF, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F, 0x10,
-    /*17(H)     18(J) 19(K)       20(M) 21(N)       */
-    0x11, 0xFF, 0x12, 0x13, 0xFF, 0x14, 0x15, 0xFF,
-    /*22(P)23(Q)24(R) 25(S) 26(T)       27(V) 28(W) */
-    0x16, 0x17, 0x18, 0x19, 0x1A, 0xFF, 0x1B, 0x1C,
-    /*29(X)30(Y)31(Z)                               */
-    0x1D, 0x1E, 0x1F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
-    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
-    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
-    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
-    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
-    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
-    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
-    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
-    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
-    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
-    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
-];
+// UniFFI expects its scaffolding at the crate root
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!("ulid");
 
 /// Ulid
-#[derive(Debug, PartialEq, PartialOrd)]
+///
+/// Stored as 16 big endian bytes, so comparing and hashing work on the bytes
+/// and agree with the `u128` form from `From<Ulid> for u128`. It is
+/// `repr(transparent)`, with the same layout as `[u8; 16]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Binary),
+    diesel(sql_type = diesel::sql_types::Uuid)
+)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(
+        compare(PartialEq, PartialOrd),
+        derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)
+    )
+)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::Unaligned,
+        zerocopy::Immutable,
+        zerocopy::KnownLayout
+    )
+)]
 pub struct Ulid([u8; 16]);
 
+/// the 80 bits of entropy in the `u128` form
+const ENTROPY_MASK: u128 = (1 << 80) - 1;
+
 impl Ulid {
-    /// creates new Ulid from a timestamp and a custom rng
-    pub fn new<F>(timestamp: u64, rng: F) -> Ulid
-    where
-        F: Fn() -> u8,
-    {
-        let mut ans = Ulid([0; 16]);
-        ans.encode_time(timestamp);
-        ans.encode_entropy(rng);
-        ans
+    /// return the timestamp associated with the Ulid
+    pub fn timestamp(&self) -> u64 {
+        (u128::from(*self) >> 80) as u64
     }
 
-    /// encodes time in the first 6 words
-    pub fn encode_time(&mut self, timestamp: u64) {
-        self.0[0] = (timestamp >> 40) as u8;
-        self.0[1] = (timestamp >> 32) as u8;
-        self.0[2] = (timestamp >> 24) as u8;
-        self.0[3] = (timestamp >> 16) as u8;
-        self.0[4] = (timestamp >> 8) as u8;
-        self.0[5] = timestamp as u8;
+    /// returns the timestamp in milliseconds since the unix epoch, for a Ulid
+    /// whose timestamp counts from `epoch`, as created with an `EpochClock`, or
+    /// `None` if it does not fit in a `u64`
+    pub fn timestamp_with_epoch(&self, epoch: u64) -> Option<u64> {
+        self.timestamp().checked_add(epoch)
     }
 
-    /// encodes entropy in the last 10 words
-    pub fn encode_entropy<F>(&mut self, rng: F)
-    where
-        F: Fn() -> u8,
-    {
-        self.0[6] = rng();
-        self.0[7] = rng();
-        self.0[8] = rng();
-        self.0[9] = rng();
-        self.0[10] = rng();
-        self.0[11] = rng();
-        self.0[12] = rng();
-        self.0[13] = rng();
-        self.0[14] = rng();
-        self.0[15] = rng();
+    /// returns the Ulid with its timestamp moved from counting from `from` to
+    /// counting from `to`, keeping the entropy, or `None` if it does not fit
+    /// in 48 bits
+    ///
+    /// With `to` at 0, converts to a Ulid any other system can read.
+    pub fn rebase_epoch(&self, from: u64, to: u64) -> Option<Ulid> {
+        self.with_timestamp(self.timestamp().checked_add(from)?.checked_sub(to)?)
     }
 
-    /// create a string representation of the stored ULID
+    /// returns the node ID in the first `len` bytes of the entropy, as set by
+    /// `Generator::with_node`
     ///
-    /// https://github.com/suyash/val/blob/master/ulid_uint128.hh#L253
-    pub fn marshal(&self) -> [u8; 26] {
-        let mut ans = [0; 26];
-        let val = self.0;
-
-        // timestamp
-        ans[0] = ENCODING[(((val[0] & 224) >> 5) as usize) as usize];
-        ans[1] = ENCODING[(val[0] & 31) as usize];
-        ans[2] = ENCODING[((val[1] & 248) >> 3) as usize];
-        ans[3] = ENCODING[(((val[1] & 7) << 2) | ((val[2] & 192) >> 6)) as usize];
-        ans[4] = ENCODING[((val[2] & 62) >> 1) as usize];
-        ans[5] = ENCODING[(((val[2] & 1) << 4) | ((val[3] & 240) >> 4)) as usize];
-        ans[6] = ENCODING[(((val[3] & 15) << 1) | ((val[4] & 128) >> 7)) as usize];
-        ans[7] = ENCODING[((val[4] & 124) >> 2) as usize];
-        ans[8] = ENCODING[(((val[4] & 3) << 3) | ((val[5] & 224) >> 5)) as usize];
-        ans[9] = ENCODING[(val[5] & 31) as usize];
-
-        // entropy
-        ans[10] = ENCODING[((val[6] & 248) >> 3) as usize];
-        ans[11] = ENCODING[(((val[6] & 7) << 2) | ((val[7] & 192) >> 6)) as usize];
-        ans[12] = ENCODING[((val[7] & 62) >> 1) as usize];
-        ans[13] = ENCODING[(((val[7] & 1) << 4) | ((val[8] & 240) >> 4)) as usize];
-        ans[14] = ENCODING[(((val[8] & 15) << 1) | ((val[9] & 128) >> 7)) as usize];
-        ans[15] = ENCODING[((val[9] & 124) >> 2) as usize];
-        ans[16] = ENCODING[(((val[9] & 3) << 3) | ((val[10] & 224) >> 5)) as usize];
-        ans[17] = ENCODING[(val[10] & 31) as usize];
-        ans[18] = ENCODING[((val[11] & 248) >> 3) as usize];
-        ans[19] = ENCODING[(((val[11] & 7) << 2) | ((val[12] & 192) >> 6)) as usize];
-        ans[20] = ENCODING[((val[12] & 62) >> 1) as usize];
-        ans[21] = ENCODING[(((val[12] & 1) << 4) | ((val[13] & 240) >> 4)) as usize];
-        ans[22] = ENCODING[(((val[13] & 15) << 1) | ((val[14] & 128) >> 7)) as usize];
-        ans[23] = ENCODING[((val[14] & 124) >> 2) as usize];
-        ans[24] = ENCODING[(((val[14] & 3) << 3) | ((val[15] & 224) >> 5)) as usize];
-        ans[25] = ENCODING[(val[15] & 31) as usize];
-
-        ans
+    /// # Panics
+    ///
+    /// if `len` is greater than 10
+    pub fn node_id(&self, len: usize) -> &[u8] {
+        &self.0[6..6 + len]
     }
 
-    /// unmarshals a string-like into a ULID
-    pub fn unmarshal<S>(s: S) -> Result<Ulid, UlidError>
-    where
-        S: AsRef<[u8]>,
-    {
-        let s = s.as_ref();
-
-        if s.len() != 26 {
-            return Err(UlidError::InvalidLength);
-        }
-
-        let mut val = [0; 16];
-
-        // timestamp
-        val[0] = (Self::unmarshal_word(s[0])? << 5) | Self::unmarshal_word(s[1])?;
-        val[1] = (Self::unmarshal_word(s[2])? << 3) | (Self::unmarshal_word(s[3])? >> 2);
-        val[2] = (Self::unmarshal_word(s[3])? << 6)
-            | (Self::unmarshal_word(s[4])? << 1)
-            | (Self::unmarshal_word(s[5])? >> 4);
-        val[3] = (Self::unmarshal_word(s[5])? << 4) | (Self::unmarshal_word(s[6])? >> 1);
-        val[4] = (Self::unmarshal_word(s[6])? << 7)
-            | (Self::unmarshal_word(s[7])? << 2)
-            | (Self::unmarshal_word(s[8])? >> 3);
-        val[5] = (Self::unmarshal_word(s[8])? << 5) | Self::unmarshal_word(s[9])?;
-
-        // entropy
-        val[6] = (Self::unmarshal_word(s[10])? << 3) | (Self::unmarshal_word(s[11])? >> 2);
-        val[7] = (Self::unmarshal_word(s[11])? << 6)
-            | (Self::unmarshal_word(s[12])? << 1)
-            | (Self::unmarshal_word(s[13])? >> 4);
-        val[8] = (Self::unmarshal_word(s[13])? << 4) | (Self::unmarshal_word(s[14])? >> 1);
-        val[9] = (Self::unmarshal_word(s[14])? << 7)
-            | (Self::unmarshal_word(s[15])? << 2)
-            | (Self::unmarshal_word(s[16])? >> 3);
-        val[10] = (Self::unmarshal_word(s[16])? << 5) | Self::unmarshal_word(s[17])?;
-        val[11] = (Self::unmarshal_word(s[18])? << 3) | (Self::unmarshal_word(s[19])? >> 2);
-        val[12] = (Self::unmarshal_word(s[19])? << 6)
-            | (Self::unmarshal_word(s[20])? << 1)
-            | (Self::unmarshal_word(s[21])? >> 4);
-        val[13] = (Self::unmarshal_word(s[21])? << 4) | (Self::unmarshal_word(s[22])? >> 1);
-        val[14] = (Self::unmarshal_word(s[22])? << 7)
-            | (Self::unmarshal_word(s[23])? << 2)
-            | (Self::unmarshal_word(s[24])? >> 3);
-        val[15] = (Self::unmarshal_word(s[24])? << 5) | Self::unmarshal_word(s[25])?;
-
-        Ok(Ulid(val))
+    /// returns the lane ID after a node ID of `node_len` bytes, as set by
+    /// `Generator::with_lane` with `lanes` lanes
+    ///
+    /// # Panics
+    ///
+    /// if `lanes` is not a power of two up to 256, or `node_len` is greater
+    /// than 8
+    pub fn lane_id(&self, node_len: usize, lanes: usize) -> usize {
+        assert!(
+            lanes.is_power_of_two() && lanes <= 256,
+            "lanes not a power of two up to 256"
+        );
+        assert!(node_len <= 8, "node ID longer than 8 bytes");
+
+        let shift = 80 - 8 * node_len as u32 - lanes.trailing_zeros();
+        ((u128::from(*self) >> shift) as usize) & (lanes - 1)
     }
 
-    fn unmarshal_word(x: u8) -> Result<u8, UlidError> {
-        if DECODING[x as usize] == 0xFF {
-            Err(UlidError::InvalidCharacter)
-        } else {
-            Ok(DECODING[x as usize])
-        }
+    /// returns the smallest Ulid for a timestamp, with all of its entropy zero
+    ///
+    /// Along with `max_for_timestamp`, turns a time range into a range of Ulids
+    /// to scan. Only the last 48 bits of the timestamp are kept.
+    pub const fn min_for_timestamp(timestamp: u64) -> Ulid {
+        Ulid(((timestamp as u128) << 80).to_be_bytes())
     }
 
-    /// return the timestamp associated with the Ulid
-    pub fn timestamp(&self) -> u64 {
-        let ans: u64 = 0;
-        let ans = (ans << 8) | self.0[0] as u64;
-        let ans = (ans << 8) | self.0[1] as u64;
-        let ans = (ans << 8) | self.0[2] as u64;
-        let ans = (ans << 8) | self.0[3] as u64;
-        let ans = (ans << 8) | self.0[4] as u64;
-        (ans << 8) | self.0[5] as u64
+    /// returns the largest Ulid for a timestamp, with all of its entropy set
+    pub const fn max_for_timestamp(timestamp: u64) -> Ulid {
+        Ulid(((timestamp as u128) << 80 | ENTROPY_MASK).to_be_bytes())
     }
 }
 
@@ -221,69 +284,66 @@ impl From<[u8; 16]> for Ulid {
 }
 
 impl TryFrom<&[u8]> for Ulid {
-    type Error = std::array::TryFromSliceError;
+    type Error = UlidError;
 
     fn try_from(f: &[u8]) -> Result<Self, Self::Error> {
-        Ok(Ulid(f.try_into()?))
+        f.try_into()
+            .map(Ulid)
+            .map_err(|_| UlidError::InvalidBufferLength { len: f.len() })
     }
 }
 
-impl TryFrom<String> for Ulid {
-    type Error = UlidError;
-
-    fn try_from(s: String) -> Result<Self, Self::Error> {
-        Ulid::unmarshal(s)
+impl From<u128> for Ulid {
+    fn from(n: u128) -> Self {
+        Ulid(n.to_be_bytes())
     }
 }
 
-impl TryFrom<&str> for Ulid {
-    type Error = UlidError;
-
-    fn try_from(s: &str) -> Result<Self, Self::Error> {
-        Ulid::unmarshal(s)
+impl From<Ulid> for u128 {
+    fn from(u: Ulid) -> Self {
+        u128::from_be_bytes(u.0)
     }
 }
 
-impl Into<[u8; 16]> for Ulid {
-    fn into(self) -> [u8; 16] {
-        self.0
+impl From<Ulid> for [u8; 16] {
+    fn from(u: Ulid) -> Self {
+        u.0
     }
 }
 
-impl<'a> Into<&'a [u8]> for &'a Ulid {
-    fn into(self) -> &'a [u8] {
-        &self.0
+impl<'a> From<&'a Ulid> for &'a [u8] {
+    fn from(u: &'a Ulid) -> Self {
+        &u.0
     }
 }
 
-impl Into<Vec<u8>> for Ulid {
-    fn into(self) -> Vec<u8> {
-        self.0.to_vec()
+#[cfg(feature = "alloc")]
+impl From<Ulid> for Vec<u8> {
+    fn from(u: Ulid) -> Self {
+        u.0.to_vec()
     }
 }
 
-impl TryInto<String> for Ulid {
-    type Error = std::string::FromUtf8Error;
-
-    fn try_into(self) -> Result<String, Self::Error> {
-        Ok(String::from_utf8(self.marshal().to_vec())?)
+impl PartialEq<[u8; 16]> for Ulid {
+    fn eq(&self, other: &[u8; 16]) -> bool {
+        self.0 == *other
     }
 }
 
-impl ToString for Ulid {
-    fn to_string(&self) -> String {
-        String::from_utf8(self.marshal().to_vec()).unwrap()
+impl PartialEq<Ulid> for [u8; 16] {
+    fn eq(&self, other: &Ulid) -> bool {
+        *self == other.0
     }
 }
 
-/// errors
-#[derive(Error, Debug)]
-pub enum UlidError {
-    /// parsing error
-    #[error("invalid length for unmarshal")]
-    InvalidLength,
+impl PartialEq<u128> for Ulid {
+    fn eq(&self, other: &u128) -> bool {
+        u128::from(*self) == *other
+    }
+}
 
-    /// parsing error
-    #[error("invalid character encountered while parsing")]
-    InvalidCharacter,
+impl PartialEq<Ulid> for u128 {
+    fn eq(&self, other: &Ulid) -> bool {
+        *self == u128::from(*other)
+    }
 }
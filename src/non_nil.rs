@@ -0,0 +1,48 @@
+//! a Ulid that is never nil, for compact optional IDs
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::num::NonZeroU128;
+
+use crate::Ulid;
+
+/// a Ulid that is not all zeros, so that `Option<NonNilUlid>` takes 16 bytes
+/// like `Ulid` itself
+///
+/// Orders and hashes like the `Ulid` it holds. Only the Ulid with a timestamp
+/// of 0 and zero entropy is nil. Generators can produce it, for example from a
+/// clock at 0 and all zero entropy, so `new` rejects it, and that check is what
+/// keeps the niche sound.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NonNilUlid(NonZeroU128);
+
+impl NonNilUlid {
+    /// creates new NonNilUlid, or returns `None` for the nil Ulid
+    pub fn new(ulid: Ulid) -> Option<NonNilUlid> {
+        NonZeroU128::new(u128::from(ulid)).map(NonNilUlid)
+    }
+
+    /// returns the Ulid
+    pub fn get(self) -> Ulid {
+        Ulid::from(self.0.get())
+    }
+}
+
+// hashes the bytes of the Ulid, where `NonZeroU128` would hash a `u128`
+impl Hash for NonNilUlid {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.get().hash(state)
+    }
+}
+
+impl From<NonNilUlid> for Ulid {
+    fn from(u: NonNilUlid) -> Self {
+        u.get()
+    }
+}
+
+impl fmt::Display for NonNilUlid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
@@ -0,0 +1,202 @@
+//! a `Generator` saving its last Ulid, so that Ulids keep increasing across
+//! restarts of the process
+
+use std::convert::TryFrom;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::{Clock, EntropySource, Generator, GeneratorStats, SystemClock, Ulid, UlidError};
+
+/// where a `PersistentGenerator` keeps its last Ulid
+///
+/// Implemented by `FileStore`, and by `Option<Ulid>` in memory, for tests.
+pub trait GeneratorStore {
+    /// reads the last Ulid saved, or `None` if nothing was saved yet
+    fn load(&mut self) -> io::Result<Option<Ulid>>;
+
+    /// saves the last Ulid, which must be durable once this returns
+    fn save(&mut self, ulid: Ulid) -> io::Result<()>;
+}
+
+impl GeneratorStore for Option<Ulid> {
+    fn load(&mut self) -> io::Result<Option<Ulid>> {
+        Ok(*self)
+    }
+
+    fn save(&mut self, ulid: Ulid) -> io::Result<()> {
+        *self = Some(ulid);
+        Ok(())
+    }
+}
+
+/// a `GeneratorStore` keeping the 16 bytes of the last Ulid in a file
+///
+/// Each save writes a temporary file next to it, syncs it to disk, renames it
+/// over the file, and on Unix syncs the directory so the rename survives a
+/// crash too. A crash leaves either the old or the new Ulid.
+#[derive(Clone, Debug)]
+pub struct FileStore {
+    path: PathBuf,
+    tmp: PathBuf,
+}
+
+impl FileStore {
+    /// creates new FileStore at `path`, which is created on the first save
+    pub fn new<P>(path: P) -> FileStore
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_path_buf();
+        let mut tmp = path.clone().into_os_string();
+        tmp.push(".tmp");
+
+        FileStore {
+            path,
+            tmp: tmp.into(),
+        }
+    }
+
+    /// returns the path of the file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl GeneratorStore for FileStore {
+    /// Fails with `InvalidData` when the file does not hold 16 bytes.
+    fn load(&mut self) -> io::Result<Option<Ulid>> {
+        let bytes = match fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        Ulid::try_from(&bytes[..])
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn save(&mut self, ulid: Ulid) -> io::Result<()> {
+        let mut file = fs::File::create(&self.tmp)?;
+        file.write_all(&ulid.0)?;
+        file.sync_all()?;
+        fs::rename(&self.tmp, &self.path)?;
+
+        // the rename is only durable once the directory is synced
+        #[cfg(unix)]
+        {
+            let dir = match self.path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir,
+                _ => Path::new("."),
+            };
+            fs::File::open(dir)?.sync_all()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// a `Generator` that resumes after the Ulid in a `GeneratorStore` when
+/// created, and saves each Ulid there before returning it
+///
+/// After a restart, or a crash, the Ulids stay greater than every Ulid from
+/// before, even if the clock moved backwards in between. Saving to a
+/// `FileStore` syncs to disk for every Ulid, which limits how many can be
+/// created per second.
+#[derive(Debug)]
+pub struct PersistentGenerator<S, C = SystemClock> {
+    generator: Generator<C>,
+    store: S,
+}
+
+impl<S> PersistentGenerator<S>
+where
+    S: GeneratorStore,
+{
+    /// creates new PersistentGenerator, resuming after the Ulid in `store`
+    pub fn open(store: S) -> io::Result<PersistentGenerator<S>> {
+        PersistentGenerator::with_generator(Generator::new(), store)
+    }
+}
+
+impl<S, C> PersistentGenerator<S, C>
+where
+    S: GeneratorStore,
+{
+    /// creates new PersistentGenerator from a configured `Generator`, resuming
+    /// after the Ulid in `store`
+    pub fn with_generator(
+        generator: Generator<C>,
+        mut store: S,
+    ) -> io::Result<PersistentGenerator<S, C>> {
+        let generator = match store.load()? {
+            Some(previous) => generator.resume_after(previous),
+            None => generator,
+        };
+
+        Ok(PersistentGenerator { generator, store })
+    }
+
+    /// creates the next Ulid from a timestamp and a source of entropy, like
+    /// `Generator::create`
+    ///
+    /// A `UlidError` is returned as an `io::Error` of kind `Other` wrapping it.
+    pub fn create<E>(&mut self, timestamp: u64, entropy: E) -> io::Result<Ulid>
+    where
+        E: EntropySource,
+    {
+        let ans = self.generator.create(timestamp, entropy);
+        self.save(ans)
+    }
+
+    /// returns the last Ulid created, or resumed after
+    pub fn last(&self) -> Option<Ulid> {
+        self.generator.last()
+    }
+
+    /// returns the counters since the PersistentGenerator was created, see
+    /// `Generator::stats`
+    pub fn stats(&self) -> GeneratorStats {
+        self.generator.stats()
+    }
+
+    /// returns the store
+    pub fn into_store(self) -> S {
+        self.store
+    }
+
+    fn save(&mut self, ans: Result<Ulid, UlidError>) -> io::Result<Ulid> {
+        let ans = ans.map_err(io::Error::other)?;
+        self.store.save(ans)?;
+        Ok(ans)
+    }
+}
+
+impl<S, C> PersistentGenerator<S, C>
+where
+    S: GeneratorStore,
+    C: Clock,
+{
+    /// creates the next Ulid from the current time in milliseconds and a
+    /// source of entropy, like `Generator::now`
+    pub fn now<E>(&mut self, entropy: E) -> io::Result<Ulid>
+    where
+        E: EntropySource,
+    {
+        let ans = self.generator.now(entropy);
+        self.save(ans)
+    }
+
+    /// creates the next Ulid from the current time in milliseconds and entropy
+    /// from the operating system, like `Generator::generate`
+    ///
+    /// # Panics
+    ///
+    /// if the operating system fails to provide entropy
+    #[cfg(feature = "getrandom")]
+    pub fn generate(&mut self) -> io::Result<Ulid> {
+        let ans = self.generator.generate();
+        self.save(ans)
+    }
+}
@@ -0,0 +1,118 @@
+//! Ulids created ahead of time on a background thread, for hot paths that
+//! cannot afford the clock and the entropy
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crossbeam_queue::ArrayQueue;
+
+use crate::{BufferedEntropy, Generator, OverflowPolicy, Ulid};
+
+/// how long the background thread sleeps once the pool is full
+const REFILL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// a pool of Ulids kept full by a background thread, handed out with `pop`
+///
+/// `pop` takes a Ulid from a lock-free ring buffer without reading the clock
+/// or any entropy, so it has no system calls. The Ulids come from one
+/// `Generator`, and are taken in the order they were created.
+///
+/// The timestamp of a Ulid is when it was put in the pool, which can be long
+/// before it is taken when the pool is seldom used. The background thread
+/// stops when the pool is dropped.
+#[derive(Debug)]
+pub struct UlidPool {
+    shared: Arc<Shared>,
+    filler: Option<JoinHandle<()>>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    queue: ArrayQueue<Ulid>,
+    stop: AtomicBool,
+}
+
+impl UlidPool {
+    /// creates new UlidPool holding up to `capacity` Ulids, and starts the
+    /// background thread filling it
+    ///
+    /// # Panics
+    ///
+    /// if `capacity` is zero, or the thread cannot be spawned
+    pub fn new(capacity: usize) -> UlidPool {
+        let shared = Arc::new(Shared {
+            queue: ArrayQueue::new(capacity),
+            stop: AtomicBool::new(false),
+        });
+
+        let filler = {
+            let shared = shared.clone();
+            thread::Builder::new()
+                .name("ulid-pool".into())
+                .spawn(move || fill(&shared))
+                .expect("failed to spawn the ulid pool thread")
+        };
+
+        UlidPool {
+            shared,
+            filler: Some(filler),
+        }
+    }
+
+    /// takes the next Ulid, or returns `None` if the pool is empty because
+    /// Ulids are taken faster than the background thread creates them
+    pub fn pop(&self) -> Option<Ulid> {
+        self.shared.queue.pop()
+    }
+
+    /// returns how many Ulids the pool holds
+    pub fn len(&self) -> usize {
+        self.shared.queue.len()
+    }
+
+    /// returns whether the pool is empty
+    pub fn is_empty(&self) -> bool {
+        self.shared.queue.is_empty()
+    }
+
+    /// returns how many Ulids the pool can hold
+    pub fn capacity(&self) -> usize {
+        self.shared.queue.capacity()
+    }
+}
+
+impl Drop for UlidPool {
+    fn drop(&mut self) {
+        self.shared.stop.store(true, Ordering::Relaxed);
+
+        if let Some(filler) = self.filler.take() {
+            filler.thread().unpark();
+            // a panic on the thread has nothing left to clean up
+            let _ = filler.join();
+        }
+    }
+}
+
+/// the background thread, topping the pool up until it is dropped
+fn fill(shared: &Shared) {
+    // waits for the next millisecond in place of failing on overflow
+    let mut generator = Generator::new().with_overflow(OverflowPolicy::Spin);
+    let mut entropy = BufferedEntropy::new();
+
+    while !shared.stop.load(Ordering::Relaxed) {
+        while !shared.queue.is_full() {
+            match generator.now(&mut entropy) {
+                Ok(ulid) => {
+                    if shared.queue.push(ulid).is_err() {
+                        break;
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+
+        thread::park_timeout(REFILL_INTERVAL);
+    }
+}
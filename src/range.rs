@@ -0,0 +1,123 @@
+//! iteration over every Ulid between two bounds
+
+use core::convert::TryFrom;
+use core::ops::{Range, RangeInclusive};
+
+use crate::Ulid;
+
+/// an iterator over the Ulids from `start` to `end`, both included, in
+/// increasing order
+///
+/// Steps by incrementing the `u128` form, so once the entropy of a millisecond
+/// is exhausted it continues at the start of the next one. Also created from a
+/// `Range<Ulid>` or `RangeInclusive<Ulid>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UlidRange {
+    front: u128,
+    back: u128,
+    exhausted: bool,
+}
+
+impl UlidRange {
+    /// creates new UlidRange from `start` to `end`, both included, which is
+    /// empty when `start` is greater than `end`
+    pub fn new(start: Ulid, end: Ulid) -> UlidRange {
+        UlidRange {
+            front: u128::from(start),
+            back: u128::from(end),
+            exhausted: start > end,
+        }
+    }
+}
+
+impl From<RangeInclusive<Ulid>> for UlidRange {
+    fn from(range: RangeInclusive<Ulid>) -> Self {
+        let (start, end) = range.into_inner();
+        UlidRange::new(start, end)
+    }
+}
+
+impl From<Range<Ulid>> for UlidRange {
+    fn from(range: Range<Ulid>) -> Self {
+        match u128::from(range.end).checked_sub(1) {
+            Some(end) if range.start < range.end => UlidRange::new(range.start, Ulid::from(end)),
+            _ => UlidRange {
+                front: 0,
+                back: 0,
+                exhausted: true,
+            },
+        }
+    }
+}
+
+impl Iterator for UlidRange {
+    type Item = Ulid;
+
+    fn next(&mut self) -> Option<Ulid> {
+        if self.exhausted {
+            return None;
+        }
+
+        let ans = self.front;
+        if ans == self.back {
+            self.exhausted = true;
+        } else {
+            self.front += 1;
+        }
+
+        Some(Ulid::from(ans))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.exhausted {
+            return (0, Some(0));
+        }
+
+        match usize::try_from(self.back - self.front) {
+            Ok(n) if n < usize::MAX => (n + 1, Some(n + 1)),
+            _ => (usize::MAX, None),
+        }
+    }
+}
+
+impl DoubleEndedIterator for UlidRange {
+    fn next_back(&mut self) -> Option<Ulid> {
+        if self.exhausted {
+            return None;
+        }
+
+        let ans = self.back;
+        if ans == self.front {
+            self.exhausted = true;
+        } else {
+            self.back -= 1;
+        }
+
+        Some(Ulid::from(ans))
+    }
+}
+
+impl core::iter::FusedIterator for UlidRange {}
+
+/// lets `start..end` of Ulids iterate directly, on nightly with the `nightly`
+/// feature, stepping like `UlidRange`
+#[cfg(feature = "nightly")]
+impl core::iter::Step for Ulid {
+    fn steps_between(start: &Ulid, end: &Ulid) -> (usize, Option<usize>) {
+        match u128::from(*end).checked_sub(u128::from(*start)) {
+            Some(n) => match usize::try_from(n) {
+                Ok(n) => (n, Some(n)),
+                Err(_) => (usize::MAX, None),
+            },
+            None => (0, None),
+        }
+    }
+
+    fn forward_checked(start: Ulid, count: usize) -> Option<Ulid> {
+        u128::from(start).checked_add(count as u128).map(Ulid::from)
+    }
+
+    fn backward_checked(start: Ulid, count: usize) -> Option<Ulid> {
+        u128::from(start).checked_sub(count as u128).map(Ulid::from)
+    }
+}
@@ -0,0 +1,129 @@
+//! Ulids signed with HMAC-SHA256, so that public IDs can be checked before
+//! looking them up
+
+use core::fmt;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::{Ulid, UlidError};
+
+/// length of the tag, in bytes, the first 80 bits of the HMAC
+const TAG_LEN: usize = 10;
+
+/// signs Ulids with a secret key, and verifies the signed form
+///
+/// The signed form is the 26 characters of the Ulid followed by 16 characters
+/// for a tag, the HMAC-SHA256 of the Ulid truncated to 80 bits, in the same
+/// base32. Tags are compared in constant time.
+#[derive(Clone)]
+pub struct UlidSigner {
+    mac: Hmac<Sha256>,
+}
+
+impl UlidSigner {
+    /// creates new UlidSigner from a secret key of any length
+    pub fn new(key: &[u8]) -> UlidSigner {
+        UlidSigner {
+            // HMAC accepts keys of any length
+            mac: Hmac::new_from_slice(key).unwrap(),
+        }
+    }
+
+    /// signs a Ulid
+    pub fn sign(&self, ulid: Ulid) -> SignedUlid {
+        let mut tag = [0; TAG_LEN];
+        tag.copy_from_slice(&self.mac(ulid).finalize().into_bytes()[..TAG_LEN]);
+        SignedUlid { ulid, tag }
+    }
+
+    /// parses the 42 character signed form, failing with
+    /// `UlidError::InvalidSignature` when the tag was not made with this key
+    pub fn verify<S>(&self, s: S) -> Result<Ulid, UlidError>
+    where
+        S: AsRef<[u8]>,
+    {
+        let s = s.as_ref();
+
+        if s.len() != 42 {
+            return Err(UlidError::InvalidLength { len: s.len() });
+        }
+
+        let ulid = Ulid::unmarshal(&s[..26])?;
+        let tag = decode_tag(&s[26..])?;
+
+        self.mac(ulid)
+            .verify_truncated_left(&tag)
+            .map(|_| ulid)
+            .map_err(|_| UlidError::InvalidSignature)
+    }
+
+    fn mac(&self, ulid: Ulid) -> Hmac<Sha256> {
+        self.mac.clone().chain_update(ulid.0)
+    }
+}
+
+impl fmt::Debug for UlidSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // leaves out the keyed state
+        f.debug_struct("UlidSigner").finish_non_exhaustive()
+    }
+}
+
+/// a Ulid along with its tag from `UlidSigner::sign`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SignedUlid {
+    ulid: Ulid,
+    tag: [u8; TAG_LEN],
+}
+
+impl SignedUlid {
+    /// returns the Ulid
+    pub fn ulid(&self) -> Ulid {
+        self.ulid
+    }
+
+    /// the 42 character signed form
+    pub fn marshal(&self) -> [u8; 42] {
+        let mut ans = [0; 42];
+        ans[..26].copy_from_slice(&self.ulid.marshal());
+        ans[26..].copy_from_slice(&encode_tag(&self.tag));
+        ans
+    }
+}
+
+impl fmt::Display for SignedUlid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // marshal only ever produces ASCII
+        f.write_str(core::str::from_utf8(&self.marshal()).unwrap())
+    }
+}
+
+// the 80 bit tag is the size of the entropy, so it is written as the last 16
+// characters of a Ulid with a zero timestamp
+
+fn encode_tag(tag: &[u8; TAG_LEN]) -> [u8; 16] {
+    let mut bytes = [0; 16];
+    bytes[6..].copy_from_slice(tag);
+
+    let mut ans = [0; 16];
+    ans.copy_from_slice(&Ulid(bytes).marshal()[10..]);
+    ans
+}
+
+fn decode_tag(s: &[u8]) -> Result<[u8; TAG_LEN], UlidError> {
+    let mut buf = [b'0'; 26];
+    buf[10..].copy_from_slice(s);
+
+    let ulid = Ulid::unmarshal(buf).map_err(|e| match e {
+        UlidError::InvalidCharacter { index, byte } => UlidError::InvalidCharacter {
+            index: index + 16,
+            byte,
+        },
+        e => e,
+    })?;
+
+    let mut ans = [0; TAG_LEN];
+    ans.copy_from_slice(&ulid.0[6..]);
+    Ok(ans)
+}
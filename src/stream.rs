@@ -0,0 +1,137 @@
+//! fixed 16 byte Ulids over `io::Read` and `io::Write`, for journals and
+//! snapshots
+
+use std::convert::TryFrom;
+use std::io::{self, Read, Write};
+
+use crate::Ulid;
+
+/// writes Ulids to a `Write` as their 16 bytes, one after another, or as
+/// batches prefixed with their length
+///
+/// Writes go straight to the inner writer, which can be wrapped in a
+/// `BufWriter` for many small writes.
+#[derive(Debug)]
+pub struct UlidWriter<W> {
+    inner: W,
+}
+
+impl<W: Write> UlidWriter<W> {
+    /// creates new UlidWriter writing to `inner`
+    pub fn new(inner: W) -> UlidWriter<W> {
+        UlidWriter { inner }
+    }
+
+    /// writes the 16 bytes of a Ulid
+    pub fn write(&mut self, ulid: &Ulid) -> io::Result<()> {
+        self.inner.write_all(&ulid.0)
+    }
+
+    /// writes the number of Ulids as a big endian `u32`, then each of them
+    ///
+    /// Fails with `InvalidInput` for more than `u32::MAX` Ulids.
+    pub fn write_batch(&mut self, ulids: &[Ulid]) -> io::Result<()> {
+        let len = u32::try_from(ulids.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "batch too large"))?;
+
+        self.inner.write_all(&len.to_be_bytes())?;
+        for ulid in ulids {
+            self.write(ulid)?;
+        }
+
+        Ok(())
+    }
+
+    /// flushes the inner writer
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    /// returns a reference to the inner writer
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// returns the inner writer
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// reads Ulids written by `UlidWriter` from a `Read`
+///
+/// Also an iterator over the Ulids until the end of the input.
+#[derive(Debug)]
+pub struct UlidReader<R> {
+    inner: R,
+}
+
+impl<R: Read> UlidReader<R> {
+    /// creates new UlidReader reading from `inner`
+    pub fn new(inner: R) -> UlidReader<R> {
+        UlidReader { inner }
+    }
+
+    /// reads the next Ulid, or returns `None` at the end of the input
+    ///
+    /// Fails with `UnexpectedEof` when the input ends within a Ulid.
+    pub fn read(&mut self) -> io::Result<Option<Ulid>> {
+        let mut buf = [0; 16];
+        let mut filled = 0;
+
+        while filled < buf.len() {
+            match self.inner.read(&mut buf[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        match filled {
+            0 => Ok(None),
+            16 => Ok(Some(Ulid(buf))),
+            _ => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "input ended within a ulid",
+            )),
+        }
+    }
+
+    /// reads a batch written by `UlidWriter::write_batch`
+    ///
+    /// Fails with `UnexpectedEof` when the input ends within the batch.
+    pub fn read_batch(&mut self) -> io::Result<Vec<Ulid>> {
+        let mut len = [0; 4];
+        self.inner.read_exact(&mut len)?;
+        let len = u32::from_be_bytes(len) as usize;
+
+        // the length is not trusted for the allocation up front
+        let mut ans = Vec::with_capacity(len.min(4096));
+        for _ in 0..len {
+            let mut buf = [0; 16];
+            self.inner.read_exact(&mut buf)?;
+            ans.push(Ulid(buf));
+        }
+
+        Ok(ans)
+    }
+
+    /// returns a reference to the inner reader
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// returns the inner reader
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Iterator for UlidReader<R> {
+    type Item = io::Result<Ulid>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read().transpose()
+    }
+}
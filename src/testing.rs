@@ -0,0 +1,65 @@
+//! helpers for tests of code that creates Ulids, with time that only moves when
+//! told to and Ulids that are known in advance
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::{Clock, Generator, Ulid, UlidError};
+
+/// a `Clock` that stays at the time it is set to
+///
+/// Clones share the same time, so a test can keep one to advance the time of
+/// a `Generator` owning another.
+#[derive(Clone, Debug, Default)]
+pub struct MockClock {
+    millis: Arc<AtomicU64>,
+}
+
+impl MockClock {
+    /// creates new MockClock at `millis` milliseconds since the unix epoch
+    pub fn new(millis: u64) -> MockClock {
+        MockClock {
+            millis: Arc::new(AtomicU64::new(millis)),
+        }
+    }
+
+    /// sets the time, which may also move backwards
+    pub fn set(&self, millis: u64) {
+        self.millis.store(millis, Ordering::SeqCst);
+    }
+
+    /// moves the time forward by `millis` milliseconds
+    pub fn advance(&self, millis: u64) {
+        self.millis.fetch_add(millis, Ordering::SeqCst);
+    }
+}
+
+impl Clock for MockClock {
+    fn now_millis(&self) -> u64 {
+        self.millis.load(Ordering::SeqCst)
+    }
+}
+
+/// a `Generator` on a `MockClock` whose entropy is zero for the first Ulid of
+/// every millisecond
+///
+/// The n-th Ulid created within a millisecond, counting from zero, has n as
+/// its entropy, so every value is known in advance.
+#[derive(Debug)]
+pub struct MockGenerator {
+    generator: Generator<MockClock>,
+}
+
+impl MockGenerator {
+    /// creates new MockGenerator reading the time from `clock`
+    pub fn new(clock: MockClock) -> MockGenerator {
+        MockGenerator {
+            generator: Generator::with_clock(clock),
+        }
+    }
+
+    /// creates the next Ulid
+    pub fn generate(&mut self) -> Result<Ulid, UlidError> {
+        self.generator.now(|| 0)
+    }
+}
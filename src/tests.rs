@@ -1,4 +1,4 @@
-use super::Ulid;
+use super::{Generator, OverflowPolicy, Strictness, Ulid, UlidError};
 
 #[test]
 fn new() {
@@ -17,6 +17,21 @@ fn unmarshal() {
 
     let res = Ulid::unmarshal("0001C7STHC0G2O81040G20810");
     assert!(res.is_err());
+
+    // a first character above 7 would overflow the 128 bits
+    assert!(Ulid::unmarshal("7ZZZZZZZZZZZZZZZZZZZZZZZZZ").is_ok());
+    for first in [b'8', b'Z'] {
+        let mut s = *b"01ARZ3NDEKTSV4RRFFQ69G5FAV";
+        s[0] = first;
+        assert_eq!(
+            Ulid::unmarshal(s),
+            Err(UlidError::InvalidCharacter {
+                index: 0,
+                byte: first
+            })
+        );
+        assert_eq!(Ulid::unmarshal_scalar(&s), Ulid::unmarshal(s));
+    }
 }
 
 #[test]
@@ -39,3 +54,2874 @@ fn lexicographical_order() {
     assert!(ulid1 < ulid2);
     assert!(ulid2 > ulid1);
 }
+
+#[cfg(feature = "schemars")]
+#[test]
+fn json_schema() {
+    let schema = schemars::schema_for!(Ulid);
+    assert_eq!(schema.get("type").unwrap(), "string");
+    assert_eq!(
+        schema.get("pattern").unwrap(),
+        "^[0-7][0-9A-HJKMNP-TV-Z]{25}$"
+    );
+    assert_eq!(schema.get("minLength").unwrap(), 26);
+    assert_eq!(schema.get("maxLength").unwrap(), 26);
+}
+
+#[cfg(feature = "utoipa")]
+#[test]
+fn openapi_schema() {
+    use utoipa::{PartialSchema, ToSchema};
+
+    assert_eq!(Ulid::name(), "Ulid");
+
+    let schema = serde_json::to_value(Ulid::schema()).unwrap();
+    assert_eq!(schema["type"], "string");
+    assert_eq!(schema["pattern"], "^[0-7][0-9A-HJKMNP-TV-Z]{25}$");
+    assert_eq!(schema["minLength"], 26);
+    assert_eq!(schema["maxLength"], 26);
+}
+
+#[cfg(feature = "borsh")]
+#[test]
+fn borsh_roundtrip() {
+    let ulid = Ulid::new(1_469_918_176_385, || 7);
+    let bytes = borsh::to_vec(&ulid).unwrap();
+    let raw: [u8; 16] = Ulid::new(1_469_918_176_385, || 7).into();
+    assert_eq!(bytes, raw);
+
+    let decoded: Ulid = borsh::from_slice(&bytes).unwrap();
+    assert_eq!(decoded, ulid);
+
+    assert!(borsh::from_slice::<Ulid>(&bytes[..15]).is_err());
+}
+
+#[cfg(feature = "rkyv")]
+#[test]
+fn rkyv_archive() {
+    use super::ArchivedUlid;
+    use rkyv::rancor::Error;
+
+    let ulid = Ulid::new(1_469_918_176_385, || 7);
+    let bytes = rkyv::to_bytes::<Error>(&ulid).unwrap();
+
+    let archived = rkyv::access::<ArchivedUlid, Error>(&bytes).unwrap();
+    assert_eq!(*archived, ulid);
+    assert!(*archived < Ulid::new(1_469_918_176_386, || 0));
+    assert!(*archived > Ulid::new(1_469_918_176_385, || 6));
+
+    let deserialized: Ulid = rkyv::deserialize::<Ulid, Error>(archived).unwrap();
+    assert_eq!(deserialized, ulid);
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn uuid_conversion() {
+    let uuid = uuid::Uuid::parse_str("01563df3-6481-0000-0000-000000000000").unwrap();
+    let ulid = Ulid::from(uuid);
+    assert_eq!(ulid.to_string(), "01ARYZ6S410000000000000000");
+
+    let uuid: uuid::Uuid = Ulid::new(1_469_918_176_385, || 0xAB).into();
+    assert_eq!(uuid.to_string(), "01563df3-6481-abab-abab-abababababab");
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn uuid_v7() {
+    let ulid = Ulid::new(1_469_918_176_385, || 0xFF);
+    let uuid = ulid.to_uuid_v7();
+    assert_eq!(uuid.get_version(), Some(uuid::Version::SortRand));
+    assert_eq!(uuid.get_variant(), uuid::Variant::RFC4122);
+    assert_eq!(uuid.to_string(), "01563df3-6481-7fff-bfff-ffffffffffff");
+
+    // the top 6 bits of the entropy do not survive the round trip
+    let back = Ulid::from_uuid_v7(uuid).unwrap();
+    assert_eq!(back.timestamp(), 1_469_918_176_385);
+    assert_eq!(back.to_string(), "01ARYZ6S410FZZZZZZZZZZZZZZ");
+    assert_eq!(back.to_uuid_v7(), uuid);
+
+    let v4 = uuid::Uuid::parse_str("01563df3-6481-4fff-bfff-ffffffffffff").unwrap();
+    assert!(Ulid::from_uuid_v7(v4).is_err());
+}
+
+#[test]
+fn to_uuid_string() {
+    let ulid = Ulid::unmarshal("01ARYZ6S410000000000000000").unwrap();
+    assert_eq!(
+        ulid.to_uuid_string(),
+        "01563df3-6481-0000-0000-000000000000"
+    );
+
+    let ulid = Ulid::new(1_469_918_176_385, || 0xAB);
+    assert_eq!(
+        ulid.to_uuid_string(),
+        "01563df3-6481-abab-abab-abababababab"
+    );
+}
+
+#[test]
+fn parse_uuid() {
+    let ulid = Ulid::new(1_469_918_176_385, || 0xAB);
+
+    let res = Ulid::parse_uuid("01563df3-6481-abab-abab-abababababab");
+    assert_eq!(res.unwrap(), ulid);
+
+    let res = Ulid::parse_uuid("01563DF36481ABABABABABABABABABAB");
+    assert_eq!(res.unwrap(), ulid);
+
+    let res = Ulid::parse_uuid(ulid.to_uuid_string());
+    assert_eq!(res.unwrap(), ulid);
+
+    assert_eq!(
+        Ulid::parse_uuid("01563df3-6481-abab-abab-ababababab"),
+        Err(UlidError::InvalidHexLength)
+    );
+    assert_eq!(
+        Ulid::parse_uuid("01563df3-6481-abab-abab_abababababab"),
+        Err(UlidError::InvalidHexCharacter)
+    );
+    assert_eq!(
+        Ulid::parse_uuid("01563df3-6481-abab-abab-abababababag"),
+        Err(UlidError::InvalidHexCharacter)
+    );
+    assert_eq!(
+        Ulid::parse_uuid("01563df36481ababababababababababag"),
+        Err(UlidError::InvalidHexLength)
+    );
+}
+
+#[test]
+fn hex() {
+    let ulid = Ulid::new(1_469_918_176_385, || 0xAB);
+    assert_eq!(ulid.to_hex(), "01563df36481abababababababababab");
+    assert_eq!(Ulid::from_hex(ulid.to_hex()).unwrap(), ulid);
+    assert_eq!(
+        Ulid::from_hex("01563DF36481ABABABABABABABABABAB").unwrap(),
+        ulid
+    );
+
+    assert!(matches!(
+        Ulid::from_hex("01563df36481ababababababababab"),
+        Err(UlidError::InvalidHexLength)
+    ));
+    assert!(matches!(
+        Ulid::from_hex("01563df36481abababababababababaz"),
+        Err(UlidError::InvalidHexCharacter)
+    ));
+}
+
+#[test]
+fn base64url() {
+    let ulid = Ulid::new(1_469_918_176_385, || 0xAB);
+    assert_eq!(ulid.to_base64url(), "AVY982SBq6urq6urq6urqw");
+    assert_eq!(Ulid::from_base64url(ulid.to_base64url()).unwrap(), ulid);
+
+    let max = Ulid::from([0xFF; 16]);
+    assert_eq!(max.to_base64url(), "_____________________w");
+    assert_eq!(Ulid::from_base64url(max.to_base64url()).unwrap(), max);
+
+    assert!(matches!(
+        Ulid::from_base64url("AVY982SBq6urq6urq6urq"),
+        Err(UlidError::InvalidBase64Length)
+    ));
+    assert!(matches!(
+        Ulid::from_base64url("AVY982SBq6urq6urq6ur+w"),
+        Err(UlidError::InvalidBase64Character)
+    ));
+    // non-zero trailing bits
+    assert!(matches!(
+        Ulid::from_base64url("AVY982SBq6urq6urq6urqx"),
+        Err(UlidError::InvalidBase64Character)
+    ));
+}
+
+#[test]
+fn base58() {
+    let ulid = Ulid::new(1_469_918_176_385, || 0xAB);
+    assert_eq!(ulid.to_base58(), "AaLsH1SRxj4aRbh6S6QqQ");
+    assert_eq!(Ulid::from_base58(ulid.to_base58()).unwrap(), ulid);
+
+    let ulid = Ulid::new(0x3df3_6481, || 0xAB);
+    assert_eq!(ulid.to_base58(), "11PmxnLgZvYN9g3k8XrSW");
+    assert_eq!(Ulid::from_base58(ulid.to_base58()).unwrap(), ulid);
+
+    let min = Ulid::from([0; 16]);
+    assert_eq!(min.to_base58(), "1111111111111111");
+    assert_eq!(Ulid::from_base58(min.to_base58()).unwrap(), min);
+
+    let max = Ulid::from([0xFF; 16]);
+    assert_eq!(max.to_base58(), "YcVfxkQb6JRzqk5kF2tNLv");
+    assert_eq!(Ulid::from_base58(max.to_base58()).unwrap(), max);
+
+    assert!(matches!(
+        Ulid::from_base58("AaLsH1SRxj4aRbh6S6Qq"),
+        Err(UlidError::InvalidBase58Length)
+    ));
+    assert!(matches!(
+        Ulid::from_base58("1AaLsH1SRxj4aRbh6S6QqQ"),
+        Err(UlidError::InvalidBase58Length)
+    ));
+    assert!(matches!(
+        Ulid::from_base58("zzzzzzzzzzzzzzzzzzzzzzz"),
+        Err(UlidError::InvalidBase58Length)
+    ));
+    assert!(matches!(
+        Ulid::from_base58("AaLsH1SRxj4aRbh6S6Q0Q"),
+        Err(UlidError::InvalidBase58Character)
+    ));
+}
+
+#[test]
+fn fmt_radix() {
+    let ulid = Ulid::new(1_469_918_176_385, || 0xAB);
+    assert_eq!(format!("{:x}", ulid), "1563df36481abababababababababab");
+    assert_eq!(format!("{:032x}", ulid), ulid.to_hex());
+    assert_eq!(format!("{:#X}", ulid), "0x1563DF36481ABABABABABABABABABAB");
+
+    let ulid = Ulid::new(0, || 0);
+    assert_eq!(format!("{:x}", ulid), "0");
+    assert_eq!(format!("{:b}", ulid), "0");
+
+    let ulid = Ulid::from([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 5]);
+    assert_eq!(format!("{:b}", ulid), "101");
+    assert_eq!(format!("{:08b}", ulid), "00000101");
+}
+
+#[test]
+fn now() {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let millis = || {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64
+    };
+
+    let before = millis();
+    let ulid = Ulid::now(|| 4);
+    let after = millis();
+
+    assert!(before <= ulid.timestamp() && ulid.timestamp() <= after);
+    assert!(ulid.to_string().ends_with("0G2081040G208104"));
+}
+
+#[cfg(feature = "getrandom")]
+#[test]
+fn generate() {
+    let ulid = Ulid::generate();
+    let now = Ulid::now(|| 0);
+    assert!(now.timestamp() - ulid.timestamp() < 1000);
+    assert_ne!(Ulid::generate(), Ulid::generate());
+
+    // entropy comes from a buffer per thread, refilled every 409 Ulids
+    let entropy = |ulids: Vec<Ulid>| -> Vec<u128> {
+        ulids
+            .into_iter()
+            .map(|u| u128::from(u) & super::ENTROPY_MASK)
+            .collect()
+    };
+    let mut ulids = entropy((0..1000).map(|_| Ulid::generate()).collect());
+    ulids.extend(entropy(
+        std::thread::spawn(|| (0..1000).map(|_| Ulid::generate()).collect())
+            .join()
+            .unwrap(),
+    ));
+    ulids.sort_unstable();
+    ulids.dedup();
+    assert_eq!(ulids.len(), 2000);
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn ffi() {
+    use super::integrations::ffi::*;
+    use std::ffi::CStr;
+
+    let mut ulid = [0; 16];
+    let mut buf = [1; 27];
+    unsafe {
+        assert_eq!(
+            ulid_parse(
+                b"01ARYZ6S410000000000000000\0".as_ptr() as *const _,
+                ulid.as_mut_ptr()
+            ),
+            0
+        );
+        assert_eq!(ulid_timestamp(ulid.as_ptr()), 1_469_918_176_385);
+
+        ulid_to_string(ulid.as_ptr(), buf.as_mut_ptr());
+        let s = CStr::from_ptr(buf.as_ptr()).to_str().unwrap();
+        assert_eq!(s, "01ARYZ6S410000000000000000");
+
+        let before = ulid;
+        assert_eq!(
+            ulid_parse(
+                b"01ARYZ6S41000000000000000\0".as_ptr() as *const _,
+                ulid.as_mut_ptr()
+            ),
+            -1
+        );
+        assert_eq!(ulid, before);
+
+        ulid_new(ulid.as_mut_ptr());
+        assert!(ulid_timestamp(ulid.as_ptr()) > 1_469_918_176_385);
+    }
+}
+
+#[test]
+fn generator_monotonic() {
+    let mut gen = Generator::new();
+
+    let ulid1 = gen.create(1_469_918_176_385, || 0).unwrap();
+    let ulid2 = gen.create(1_469_918_176_385, || 0).unwrap();
+    let ulid3 = gen.create(1_469_918_176_384, || 0).unwrap();
+    assert_eq!(ulid1.to_string(), "01ARYZ6S410000000000000000");
+    assert_eq!(ulid2.to_string(), "01ARYZ6S410000000000000001");
+    assert_eq!(ulid3.to_string(), "01ARYZ6S410000000000000002");
+
+    let ulid4 = gen.create(1_469_918_176_386, || 0).unwrap();
+    assert_eq!(ulid4.to_string(), "01ARYZ6S420000000000000000");
+}
+
+#[test]
+fn generator_overflow() {
+    let mut gen = Generator::new();
+
+    let ulid = gen.create(1_469_918_176_385, || 0xFF).unwrap();
+    assert_eq!(ulid.to_string(), "01ARYZ6S41ZZZZZZZZZZZZZZZZ");
+    assert!(matches!(
+        gen.create(1_469_918_176_385, || 0xFF),
+        Err(UlidError::Overflow)
+    ));
+
+    let ulid = gen.create(1_469_918_176_386, || 0xFF).unwrap();
+    assert_eq!(ulid.timestamp(), 1_469_918_176_386);
+}
+
+#[test]
+fn generator_overflow_policy() {
+    use core::cell::Cell;
+
+    let mut gen = Generator::new().with_overflow(OverflowPolicy::Wrap);
+    gen.create(1_469_918_176_385, || 0xFF).unwrap();
+    let ulid = gen.create(1_469_918_176_385, || 0xFF).unwrap();
+    assert_eq!(ulid.to_string(), "01ARYZ6S410000000000000000");
+    let ulid = gen.create(1_469_918_176_385, || 0xFF).unwrap();
+    assert_eq!(ulid.to_string(), "01ARYZ6S410000000000000001");
+
+    // the node ID is kept when wrapping
+    let mut gen = Generator::new()
+        .with_node(&[0xAB])
+        .with_overflow(OverflowPolicy::Wrap);
+    gen.create(1_469_918_176_385, || 0xFF).unwrap();
+    let ulid = gen.create(1_469_918_176_385, || 0xFF).unwrap();
+    assert_eq!(ulid.node_id(1), [0xAB]);
+    assert_eq!(ulid.0[7..], [0; 9]);
+
+    // with 16 random bits left, wrapping stops before repeating the first Ulid
+    let mut gen = Generator::new()
+        .with_node(&[0xAB; 7])
+        .with_lane(0, 256)
+        .with_overflow(OverflowPolicy::Wrap);
+    let mut seen = std::collections::HashSet::new();
+    loop {
+        match gen.create(1_469_918_176_385, || 0x10) {
+            Ok(ulid) => assert!(seen.insert(ulid)),
+            Err(e) => {
+                assert_eq!(e, UlidError::Overflow);
+                break;
+            }
+        }
+    }
+    assert_eq!(seen.len(), 1 << 16);
+    assert!(gen.create(1_469_918_176_386, || 0x10).is_ok());
+
+    // the first Ulid of the millisecond is unknown after resuming
+    let last = Ulid::from(u128::from(1_469_918_176_385u64) << 80 | ((1 << 80) - 1));
+    let mut gen = Generator::new()
+        .with_overflow(OverflowPolicy::Wrap)
+        .resume_after(last);
+    assert_eq!(
+        gen.create(1_469_918_176_385, || 0),
+        Err(UlidError::Overflow)
+    );
+
+    // the clock only moves forward every third read
+    let reads = Cell::new(0);
+    let clock = || {
+        reads.set(reads.get() + 1);
+        1_469_918_176_385 + reads.get() / 3
+    };
+    let mut gen = Generator::with_clock(clock).with_overflow(OverflowPolicy::Spin);
+    let ulid = gen.now(|| 0xFF).unwrap();
+    assert_eq!(ulid.to_string(), "01ARYZ6S41ZZZZZZZZZZZZZZZZ");
+    let ulid = gen.now(|| 0xFF).unwrap();
+    assert_eq!(ulid.to_string(), "01ARYZ6S42ZZZZZZZZZZZZZZZZ");
+    assert_eq!(reads.get(), 3);
+
+    // create is given the timestamp, so it cannot wait
+    assert!(matches!(
+        gen.create(1_469_918_176_386, || 0xFF),
+        Err(UlidError::Overflow)
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn sharded_generator() {
+    use super::ShardedGenerator;
+    use std::collections::HashSet;
+
+    // each thread owns its lane, whose Ulids are strictly increasing
+    let sharded = ShardedGenerator::new(4);
+    let barrier = std::sync::Barrier::new(4);
+    let per_thread: Vec<(usize, Vec<Ulid>)> = std::thread::scope(|s| {
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut generator = sharded.claim().unwrap();
+                    let ulids: Vec<Ulid> = (0..1000)
+                        .map(|_| generator.now(rand::random::<u8>).unwrap())
+                        .collect();
+                    // holds the lane until every thread has one
+                    barrier.wait();
+                    (ulids[0].lane_id(0, 4), ulids)
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    let mut all = HashSet::new();
+    let mut lanes = HashSet::new();
+    for (lane, ulids) in &per_thread {
+        assert!(ulids.iter().all(|ulid| ulid.lane_id(0, 4) == *lane));
+        assert!(ulids.windows(2).all(|w| w[0] < w[1]));
+        all.extend(ulids.iter().copied());
+        lanes.insert(*lane);
+    }
+    assert_eq!(all.len(), 4000);
+    assert_eq!(lanes.len(), 4);
+
+    // a lane is never handed out twice while it has an owner
+    let held = sharded.into_lanes();
+    assert_eq!(held.len(), 4);
+
+    // the lane bits are never incremented into
+    let sharded = ShardedGenerator::with_clock(4, || 1_469_918_176_385);
+    assert_eq!(sharded.lanes(), 4);
+    let mut first = sharded.claim().unwrap();
+    assert_eq!(first.lane(), 0);
+    assert_eq!(first.now(|| 0xFF).unwrap().lane_id(0, 4), 0);
+    assert!(matches!(first.now(|| 0xFF), Err(UlidError::Overflow)));
+
+    let mut others: Vec<_> = (0..3).map(|_| sharded.claim().unwrap()).collect();
+    assert!(sharded.claim().is_none());
+    let ulid = others[0].now(|| 0xFF).unwrap();
+    assert_eq!(ulid.lane_id(0, 4), 1);
+
+    // a dropped lane is handed out again with its Generator, so it keeps
+    // increasing
+    drop(first);
+    let mut again = sharded.claim().unwrap();
+    assert_eq!(again.lane(), 0);
+    assert!(matches!(again.now(|| 0xFF), Err(UlidError::Overflow)));
+    drop(others);
+    assert_eq!(sharded.into_lanes().len(), 3);
+
+    let mut gen = Generator::new().with_node(&[0xAB]).with_lane(5, 8);
+    let ulid = gen.create(1_469_918_176_385, || 0).unwrap();
+    assert_eq!(ulid.node_id(1), [0xAB]);
+    assert_eq!(ulid.lane_id(1, 8), 5);
+    let next = gen.create(1_469_918_176_385, || 0).unwrap();
+    assert_eq!(u128::from(next), u128::from(ulid) + 1);
+    assert_eq!(next.lane_id(1, 8), 5);
+}
+
+#[cfg(feature = "getrandom")]
+#[test]
+fn sharded_generator_generate() {
+    use super::ShardedGenerator;
+    use std::collections::HashSet;
+
+    // each thread gets a lane on its first Ulid, and gives it back on exit,
+    // so many more threads than lanes can come and go
+    let sharded = ShardedGenerator::new(2);
+    let mut all = HashSet::new();
+    for _ in 0..16 {
+        let ulids: Vec<Ulid> = std::thread::scope(|s| {
+            s.spawn(|| (0..100).map(|_| sharded.generate().unwrap()).collect())
+                .join()
+                .unwrap()
+        });
+        assert!(ulids.windows(2).all(|w| w[0] < w[1]));
+        assert!(ulids
+            .iter()
+            .all(|u| u.lane_id(0, 2) == ulids[0].lane_id(0, 2)));
+        all.extend(ulids);
+    }
+    assert_eq!(all.len(), 1600);
+
+    // while every lane is bound to a live thread, others fail
+    let ulid = sharded.generate().unwrap();
+    let _held = sharded.claim().unwrap();
+    let other = std::thread::scope(|s| s.spawn(|| sharded.generate()).join().unwrap());
+    assert_eq!(other, Err(UlidError::NoFreeLane));
+    assert!(sharded.generate().unwrap() > ulid);
+
+    // the lanes of another ShardedGenerator on the same thread are separate
+    let other = ShardedGenerator::new(1);
+    assert_eq!(other.generate().unwrap().lane_id(0, 1), 0);
+    assert!(other.claim().is_none());
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[should_panic(expected = "lanes must be a power of two up to 256")]
+fn sharded_generator_without_lanes() {
+    super::ShardedGenerator::new(0);
+}
+
+#[test]
+fn generator_stats() {
+    let mut gen = Generator::new();
+    gen.create(1_469_918_176_385, || 0xFF).unwrap();
+
+    // failed creates are not issued
+    assert!(matches!(
+        gen.create(1_469_918_176_385, || 0xFF),
+        Err(UlidError::Overflow)
+    ));
+    assert!(matches!(
+        gen.create(1 << 48, || 0),
+        Err(UlidError::InvalidTimestamp)
+    ));
+    let stats = gen.stats();
+    assert_eq!(stats.issued, 1);
+    assert_eq!(stats.same_millisecond, 0);
+    assert_eq!(stats.overflows, 1);
+
+    gen.create(1_469_918_176_386, || 0).unwrap();
+    gen.create(1_469_918_176_386, || 0).unwrap();
+    gen.create(1_469_918_176_384, || 0).unwrap();
+
+    let stats = gen.stats();
+    assert_eq!(stats.issued, 4);
+    assert_eq!(stats.same_millisecond, 2);
+    assert_eq!(stats.overflows, 1);
+    assert_eq!(stats.clock_regressions, 1);
+
+    // a wrap is an overflow, and the Ulid is issued
+    let mut gen = Generator::new().with_overflow(OverflowPolicy::Wrap);
+    gen.create(1_469_918_176_385, || 0xFF).unwrap();
+    gen.create(1_469_918_176_385, || 0xFF).unwrap();
+    let stats = gen.stats();
+    assert_eq!(stats.issued, 2);
+    assert_eq!(stats.overflows, 1);
+}
+
+#[cfg(feature = "napi")]
+#[test]
+fn napi() {
+    use super::integrations::napi::*;
+
+    let id = ulid(Some(1_469_918_176_385.0)).unwrap();
+    assert!(id.starts_with("01ARYZ6S41"));
+    for t in [-1.0, 0.5, f64::NAN, f64::INFINITY, (1u64 << 48) as f64] {
+        assert!(ulid(Some(t)).is_err(), "{}", t);
+    }
+    assert!(ulid(Some(((1u64 << 48) - 1) as f64)).is_ok());
+    assert!(ulid(None).is_ok());
+    assert_eq!(decode_time(id.clone()).unwrap(), 1_469_918_176_385.0);
+    assert_eq!(parse(id.clone()).unwrap().len(), 16);
+    assert!(decode_time("01ARYZ6S41".to_string()).is_err());
+
+    let mut factory = monotonic_factory();
+    let ulid1 = factory.next(Some(1_469_918_176_385.0)).unwrap();
+    let ulid2 = factory.next(Some(1_469_918_176_385.0)).unwrap();
+    assert!(ulid1 < ulid2);
+    assert_eq!(ulid1[..10], ulid2[..10]);
+    assert!(factory.next(None).unwrap() > ulid2);
+    assert!(factory.next(Some(-1.0)).is_err());
+    assert!(factory.next(Some(1_125_899_906_842_624.0)).is_err());
+}
+
+#[cfg(feature = "python")]
+#[test]
+fn python() {
+    use super::integrations::python::PyUlid;
+
+    let ulid = PyUlid::parse("01ARYZ6S410000000000000000").unwrap();
+    assert_eq!(ulid.timestamp(), 1_469_918_176_385);
+    assert_eq!(ulid.__str__(), "01ARYZ6S410000000000000000");
+    assert_eq!(ulid.__repr__(), "Ulid('01ARYZ6S410000000000000000')");
+    assert!(PyUlid::parse("01ARYZ6S41000000000000000").is_err());
+
+    let later = PyUlid::new(Some(1_469_918_176_386)).unwrap();
+    assert!(ulid < later);
+    assert!(PyUlid::new(Some(1 << 50)).is_err());
+    assert_eq!(
+        PyUlid::new(Some((1 << 48) - 1)).unwrap().timestamp(),
+        (1 << 48) - 1
+    );
+    let mut bytes = [0; 16];
+    bytes.copy_from_slice(&later.__bytes__());
+    assert_eq!(PyUlid::from_bytes(bytes), later);
+}
+
+#[cfg(feature = "uniffi")]
+#[test]
+fn uniffi() {
+    use super::integrations::uniffi::{new_ulid_at, parse_ulid, ulid_timestamp, UlidGenerator};
+
+    assert_eq!(
+        ulid_timestamp("01ARYZ6S410000000000000000".to_string()).unwrap(),
+        1_469_918_176_385
+    );
+    assert_eq!(
+        parse_ulid("01ARYZ6S41000000000000000Z".to_string()).unwrap(),
+        [1, 86, 61, 243, 100, 129, 0, 0, 0, 0, 0, 0, 0, 0, 0, 31]
+    );
+    assert!(parse_ulid("01ARYZ6S4100000000000000".to_string()).is_err());
+
+    let generator = UlidGenerator::new();
+    let first = generator.next_at(1_469_918_176_385).unwrap();
+    let second = generator.next_at(1_469_918_176_385).unwrap();
+    assert!(first < second);
+    assert_eq!(&second[..10], "01ARYZ6S41");
+
+    assert_eq!(&new_ulid_at(1_469_918_176_385).unwrap()[..10], "01ARYZ6S41");
+    assert_eq!(new_ulid_at(1 << 48), Err(UlidError::InvalidTimestamp));
+    assert_eq!(generator.next_at(1 << 48), Err(UlidError::InvalidTimestamp));
+}
+
+#[test]
+fn marshal_simd() {
+    let mut inputs = vec![[0; 16], [0xFF; 16], [0xAA; 16], [0x55; 16]];
+    for bit in 0..128 {
+        inputs.push((1u128 << bit).to_be_bytes());
+    }
+    for _ in 0..1000 {
+        inputs.push(rand::random());
+    }
+
+    for input in inputs {
+        let ulid = Ulid::from(input);
+        let expected = ulid.marshal_scalar();
+        assert_eq!(ulid.marshal(), expected);
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        unsafe {
+            use super::codec::simd::x86;
+
+            if is_x86_feature_detected!("ssse3") {
+                assert_eq!(x86::marshal_ssse3(&input), expected);
+            }
+            if is_x86_feature_detected!("avx2") {
+                assert_eq!(x86::marshal_avx2(&input), expected);
+            }
+        }
+
+        #[cfg(all(target_arch = "aarch64", target_endian = "little"))]
+        unsafe {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                assert_eq!(super::codec::simd::aarch64::marshal_neon(&input), expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn unmarshal_simd() {
+    let mut inputs = vec![
+        *b"00000000000000000000000000",
+        *b"7ZZZZZZZZZZZZZZZZZZZZZZZZZ",
+    ];
+    for _ in 0..1000 {
+        inputs.push(Ulid::from(rand::random::<[u8; 16]>()).marshal());
+    }
+    for position in 0..26 {
+        for c in 0..=255 {
+            let mut input = *b"01ARYZ6S41TSV4RRFFQ69G5FAV";
+            input[position] = c;
+            inputs.push(input);
+        }
+    }
+
+    for input in inputs {
+        let scalar = Ulid::unmarshal_scalar(&input);
+        assert_eq!(Ulid::unmarshal(input), scalar);
+        let expected = scalar.ok();
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        unsafe {
+            use super::codec::simd::x86;
+
+            if is_x86_feature_detected!("ssse3") {
+                assert_eq!(x86::unmarshal_ssse3(&input), expected);
+            }
+            if is_x86_feature_detected!("avx2") {
+                assert_eq!(x86::unmarshal_avx2(&input), expected);
+            }
+        }
+
+        #[cfg(all(target_arch = "aarch64", target_endian = "little"))]
+        unsafe {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                assert_eq!(
+                    super::codec::simd::aarch64::unmarshal_neon(&input),
+                    expected
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn u128_conversions() {
+    let ulid = Ulid::unmarshal("01ARYZ6S41TSV4RRFFQ69G5FAV").unwrap();
+    let n = u128::from(ulid);
+    assert_eq!(n, 0x01563DF36481D6764C61EFB99302BD5B);
+    assert_eq!(Ulid::from(n), ulid);
+    assert_eq!((n >> 80) as u64, ulid.timestamp());
+
+    let next = Ulid::from(n + 1);
+    assert!(next > ulid);
+    assert_eq!(Ulid::from(u128::MAX).timestamp(), 0xFFFF_FFFF_FFFF);
+}
+
+#[cfg(feature = "getrandom")]
+#[test]
+fn generate_batch() {
+    let ulids = Ulid::generate_batch(1000);
+    assert_eq!(ulids.len(), 1000);
+    assert!(ulids.iter().all(|u| u.timestamp() == ulids[0].timestamp()));
+
+    let mut unique = ulids.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), 1000);
+
+    let mut ulids = [Ulid::from([0; 16]); 3];
+    Ulid::generate_into(&mut ulids);
+    assert!(ulids.iter().all(|u| u.timestamp() > 0));
+    Ulid::generate_into(&mut []);
+}
+
+#[cfg(feature = "getrandom")]
+#[test]
+fn iter() {
+    use super::UlidIter;
+    use core::sync::atomic::{AtomicU64, Ordering};
+
+    let ulids: Vec<Ulid> = UlidIter::monotonic().take(1000).collect();
+    assert_eq!(ulids.len(), 1000);
+    assert!(ulids.windows(2).all(|w| w[0] < w[1]));
+
+    for (i, ulid) in (0..3).zip(UlidIter::new()) {
+        assert!(ulid.timestamp() > 0, "{}", i);
+    }
+
+    // the clock only moves forward every third read, and the first Ulid
+    // overflows the entropy, so the iterator waits for the next millisecond
+    let reads = AtomicU64::new(0);
+    let clock = || 1_469_918_176_385 + reads.fetch_add(1, Ordering::Relaxed) / 3;
+    let last = Ulid::from((1_469_918_176_385 << 80) | ((1 << 80) - 1));
+    let mut iter = UlidIter::monotonic_with(Generator::with_clock(clock).resume_after(last));
+
+    let ulids: Vec<Ulid> = iter.by_ref().take(3).collect();
+    assert_eq!(ulids.len(), 3);
+    assert_eq!(ulids[0].timestamp(), 1_469_918_176_386);
+    assert!(last < ulids[0] && ulids.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_generate_batch() {
+    let ulids = Ulid::par_generate_batch(20_000);
+    assert_eq!(ulids.len(), 20_000);
+    assert!(ulids.iter().all(|u| u.timestamp() == ulids[0].timestamp()));
+
+    let ulids = Ulid::par_generate_batch_sorted(20_000);
+    assert_eq!(ulids.len(), 20_000);
+    assert!(ulids.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[cfg(feature = "atomic")]
+#[test]
+fn atomic_generator() {
+    use super::AtomicGenerator;
+    use std::sync::Arc;
+
+    let generator = AtomicGenerator::new();
+    let first = generator.create(1_469_918_176_385, || 0).unwrap();
+    let second = generator.create(1_469_918_176_385, || 0xFF).unwrap();
+    assert_eq!(u128::from(second), u128::from(first) + 1);
+    assert!(generator.create(1_469_918_176_384, || 0).unwrap() > second);
+    #[cfg(target_arch = "x86_64")]
+    assert_eq!(
+        AtomicGenerator::is_lock_free(),
+        is_x86_feature_detected!("cmpxchg16b")
+    );
+
+    let generator = Arc::new(AtomicGenerator::new());
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let generator = generator.clone();
+            std::thread::spawn(move || {
+                (0..10_000)
+                    .map(|_| generator.create(1_469_918_176_385, rand::random).unwrap())
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    let mut ulids = Vec::new();
+    for thread in threads {
+        let created = thread.join().unwrap();
+        assert!(created.windows(2).all(|w| w[0] < w[1]));
+        ulids.extend(created);
+    }
+
+    ulids.sort();
+    ulids.dedup();
+    assert_eq!(ulids.len(), 40_000);
+}
+
+#[cfg(all(feature = "std", feature = "getrandom"))]
+#[test]
+fn buffered_entropy() {
+    use super::{BufferedEntropy, EntropySource};
+    use core::cell::Cell;
+
+    // one read of 4 KiB from the source serves 409 Ulids, handed out in order
+    let reads = Cell::new(0);
+    let mut entropy = BufferedEntropy::from_fn(4096, |buf: &mut [u8]| {
+        assert_eq!(buf.len(), 4090);
+        reads.set(reads.get() + 1);
+        for (i, byte) in buf.iter_mut().enumerate() {
+            *byte = (i / 10) as u8;
+        }
+    });
+    assert_eq!(entropy.capacity(), 4090);
+
+    let mut gen = Generator::new();
+    for i in 0..409 {
+        let ulid = gen.create(1_469_918_176_385 + i, &mut entropy).unwrap();
+        assert_eq!(ulid.0[6..], [i as u8; 10]);
+    }
+    assert_eq!(reads.get(), 1);
+    gen.create(1_469_918_176_385 + 409, &mut entropy).unwrap();
+    assert_eq!(reads.get(), 2);
+
+    // a clone never hands out the bytes left in the original
+    let mut original = BufferedEntropy::new();
+    original.fill(&mut [0; 10]);
+    let mut clone = original.clone();
+    assert_ne!(
+        Generator::new().create(1, &mut original),
+        Generator::new().create(1, &mut clone)
+    );
+    assert_eq!(
+        format!("{:?}", clone),
+        "BufferedEntropy { capacity: 4090, .. }"
+    );
+
+    let ulid = Ulid::now(BufferedEntropy::new());
+    assert!(ulid.timestamp() > 1_469_918_176_385);
+}
+
+#[cfg(all(unix, feature = "getrandom"))]
+#[test]
+fn os_entropy_fork() {
+    use super::generator::os_entropy;
+
+    // the buffer of this thread has bytes left, which the child must not reuse
+    os_entropy();
+
+    let mut fds = [0; 2];
+    assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+
+    let pid = unsafe { libc::fork() };
+    assert!(pid >= 0);
+    if pid == 0 {
+        let entropy = os_entropy();
+        unsafe {
+            libc::write(fds[1], entropy.as_ptr().cast(), 10);
+            libc::_exit(0);
+        }
+    }
+
+    let mut child = [0u8; 10];
+    let read = unsafe { libc::read(fds[0], child.as_mut_ptr().cast(), 10) };
+    unsafe {
+        libc::waitpid(pid, core::ptr::null_mut(), 0);
+        libc::close(fds[0]);
+        libc::close(fds[1]);
+    }
+
+    assert_eq!(read, 10);
+    assert_ne!(child, os_entropy());
+}
+
+#[cfg(feature = "getrandom")]
+#[test]
+fn generate_local() {
+    let ulids: Vec<Ulid> = (0..1000).map(|_| Ulid::generate_local().unwrap()).collect();
+    assert!(ulids.windows(2).all(|w| w[0] < w[1]));
+
+    let other = std::thread::spawn(|| Ulid::generate_local().unwrap())
+        .join()
+        .unwrap();
+    assert!(!ulids.contains(&other));
+}
+
+#[test]
+fn clock() {
+    use super::{Clock, SystemClock};
+
+    let clock = || 1_469_918_176_385;
+    assert_eq!(clock.now_millis(), 1_469_918_176_385);
+    assert!(SystemClock.now_millis() > 1_469_918_176_385);
+
+    let ulid = Ulid::now_with_clock(&clock, || 0);
+    assert_eq!(ulid.marshal_scalar(), *b"01ARYZ6S410000000000000000");
+
+    let mut generator = Generator::with_clock(clock);
+    let first = generator.now(|| 0).unwrap();
+    let second = generator.now(|| 0).unwrap();
+    assert_eq!(first, ulid);
+    assert_eq!(u128::from(second), u128::from(first) + 1);
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn testing() {
+    use super::testing::{MockClock, MockGenerator};
+    use super::Clock;
+
+    let clock = MockClock::new(1_469_918_176_385);
+    let mut generator = MockGenerator::new(clock.clone());
+
+    assert_eq!(
+        generator.generate().unwrap().to_string(),
+        "01ARYZ6S410000000000000000"
+    );
+    assert_eq!(
+        generator.generate().unwrap().to_string(),
+        "01ARYZ6S410000000000000001"
+    );
+
+    clock.advance(1);
+    assert_eq!(clock.now_millis(), 1_469_918_176_386);
+    assert_eq!(
+        generator.generate().unwrap().to_string(),
+        "01ARYZ6S420000000000000000"
+    );
+
+    clock.set(1_469_918_176_385);
+    assert_eq!(
+        generator.generate().unwrap().to_string(),
+        "01ARYZ6S420000000000000001"
+    );
+}
+
+#[test]
+fn entropy_source() {
+    use super::EntropySource;
+
+    struct Fixed([u8; 10]);
+
+    impl EntropySource for Fixed {
+        fn fill(&mut self, entropy: &mut [u8; 10]) {
+            *entropy = self.0;
+        }
+    }
+
+    let mut counter = 0;
+    let ulid = Ulid::new(1_469_918_176_385, || {
+        counter += 1;
+        counter
+    });
+    assert_eq!(&ulid.0[6..], &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+
+    let ulid = Ulid::new(1_469_918_176_385, Fixed([0xFF; 10]));
+    assert_eq!(ulid.marshal_scalar(), *b"01ARYZ6S41ZZZZZZZZZZZZZZZZ");
+    assert!(Generator::new()
+        .create(1_469_918_176_385, Fixed([0xFF; 10]))
+        .is_ok());
+}
+
+#[cfg(feature = "rand_core")]
+#[test]
+fn rng_entropy() {
+    use super::RngEntropy;
+
+    struct Counter(u8);
+
+    impl rand_core::RngCore for Counter {
+        fn next_u32(&mut self) -> u32 {
+            rand_core::impls::next_u32_via_fill(self)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            rand_core::impls::next_u64_via_fill(self)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for byte in dest {
+                self.0 += 1;
+                *byte = self.0;
+            }
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    let mut rng = Counter(0);
+    let first = Ulid::new(1_469_918_176_385, RngEntropy(&mut rng));
+    let second = Ulid::new(1_469_918_176_385, RngEntropy(&mut rng));
+    assert_eq!(&first.0[6..], &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+    assert_eq!(&second.0[6..], &[11, 12, 13, 14, 15, 16, 17, 18, 19, 20]);
+}
+
+#[cfg(feature = "seeded")]
+#[test]
+fn seeded_generator() {
+    use super::SeededGenerator;
+
+    let clock = || 1_469_918_176_385;
+    let mut first = SeededGenerator::seed_from_u64(42, clock);
+    let mut second = SeededGenerator::seed_from_u64(42, clock);
+    let mut other = SeededGenerator::seed_from_u64(43, clock);
+
+    for _ in 0..3 {
+        let ulid = first.generate().unwrap();
+        assert_eq!(second.generate().unwrap(), ulid);
+        assert_ne!(other.generate().unwrap(), ulid);
+    }
+
+    assert_eq!(
+        first.create(1_469_918_176_386).unwrap(),
+        second.create(1_469_918_176_386).unwrap()
+    );
+}
+
+#[test]
+fn timestamp_bounds() {
+    let min = Ulid::min_for_timestamp(1_469_918_176_385);
+    let max = Ulid::max_for_timestamp(1_469_918_176_385);
+    assert_eq!(min.marshal_scalar(), *b"01ARYZ6S410000000000000000");
+    assert_eq!(max.marshal_scalar(), *b"01ARYZ6S41ZZZZZZZZZZZZZZZZ");
+    assert_eq!(min.timestamp(), 1_469_918_176_385);
+    assert_eq!(max.timestamp(), 1_469_918_176_385);
+
+    let ulid = Ulid::new(1_469_918_176_385, rand::random);
+    assert!(min <= ulid && ulid <= max);
+    assert!(max < Ulid::min_for_timestamp(1_469_918_176_386));
+    assert_eq!(Ulid::max_for_timestamp(u64::MAX), Ulid::from(u128::MAX));
+}
+
+#[test]
+fn range() {
+    use super::UlidRange;
+
+    let max = Ulid::max_for_timestamp(1_469_918_176_385);
+    let start = Ulid::from(u128::from(max) - 1);
+    let end = Ulid::from(u128::from(max) + 2);
+
+    let ulids: Vec<Ulid> = UlidRange::new(start, end).collect();
+    assert_eq!(ulids.len(), 4);
+    assert_eq!(ulids[1], max);
+    assert_eq!(ulids[2], Ulid::min_for_timestamp(1_469_918_176_386));
+    assert_eq!(UlidRange::new(start, end).size_hint(), (4, Some(4)));
+
+    let reversed: Vec<Ulid> = UlidRange::new(start, end).rev().collect();
+    assert_eq!(reversed, ulids.iter().rev().cloned().collect::<Vec<_>>());
+
+    assert_eq!(UlidRange::from(start..end).count(), 3);
+    assert_eq!(UlidRange::from(start..=end).next_back(), Some(end));
+    assert_eq!(UlidRange::from(end..start).next(), None);
+    assert_eq!(UlidRange::from(start..start).next(), None);
+
+    let all = Ulid::from(u128::MAX);
+    assert_eq!(UlidRange::new(all, all).collect::<Vec<_>>(), vec![all]);
+}
+
+#[test]
+fn timestamp_prefix() {
+    let ulid = Ulid::new(1_469_918_176_385, rand::random);
+    assert_eq!(&ulid.timestamp_prefix(), b"01ARYZ6S41");
+    assert_eq!(ulid.timestamp_prefix(), ulid.marshal()[..10]);
+    assert_eq!(
+        Ulid::prefix_for_timestamp(1_469_918_176_385),
+        *b"01ARYZ6S41"
+    );
+    assert_eq!(Ulid::prefix_for_timestamp(0), *b"0000000000");
+    assert_eq!(Ulid::prefix_for_timestamp(u64::MAX), *b"7ZZZZZZZZZ");
+}
+
+#[test]
+fn generator_node() {
+    let mut generator = Generator::with_clock(|| 1_469_918_176_385).with_node(&[0xAB, 0xCD]);
+    assert_eq!(generator.node_id(), &[0xAB, 0xCD]);
+
+    let first = generator.now(|| 0xFF).unwrap();
+    assert_eq!(first.node_id(2), &[0xAB, 0xCD]);
+    assert_eq!(&first.0[8..], &[0xFF; 8]);
+
+    // only the random bytes are incremented
+    assert!(matches!(generator.now(|| 0), Err(UlidError::Overflow)));
+
+    let mut generator = Generator::new().with_node(&[7]);
+    let first = generator.create(1, || 0).unwrap();
+    let second = generator.create(1, || 0).unwrap();
+    assert_eq!(second.node_id(1), &[7]);
+    assert_eq!(u128::from(second), u128::from(first) + 1);
+    assert!(Generator::new().node_id().is_empty());
+}
+
+#[cfg(feature = "ksuid")]
+#[test]
+fn ksuid() {
+    // 0ujtsYcgvSTl8PAuAdqWYSMnLOv
+    let ksuid = [
+        0x06, 0x69, 0xF7, 0xEF, 0xB5, 0xA1, 0xCD, 0x34, 0xB5, 0xF9, 0x9D, 0x11, 0x54, 0xFB, 0x68,
+        0x53, 0x34, 0x5C, 0x97, 0x35,
+    ];
+
+    let ulid = Ulid::from_ksuid(&ksuid);
+    assert_eq!(ulid.timestamp(), 1_507_608_047_000);
+    assert_eq!(&ulid.0[6..], &ksuid[4..14]);
+
+    let converted = ulid.to_ksuid().unwrap();
+    assert_eq!(converted[..14], ksuid[..14]);
+    assert_eq!(converted[14..], [0; 6]);
+
+    let ulid = Ulid::new(1_507_608_047_999, || 0);
+    assert_eq!(
+        Ulid::from_ksuid(&ulid.to_ksuid().unwrap()).timestamp(),
+        1_507_608_047_000
+    );
+    assert!(Ulid::new(1_399_999_999_999, || 0).to_ksuid().is_err());
+    assert!(Ulid::new(5_694_967_296_000, || 0).to_ksuid().is_err());
+    assert!(Ulid::new(5_694_967_295_999, || 0).to_ksuid().is_ok());
+}
+
+#[cfg(feature = "objectid")]
+#[test]
+fn object_id() {
+    // 507f1f77bcf86cd799439011
+    let object_id = [
+        0x50, 0x7F, 0x1F, 0x77, 0xBC, 0xF8, 0x6C, 0xD7, 0x99, 0x43, 0x90, 0x11,
+    ];
+
+    let ulid = Ulid::from_object_id(&object_id);
+    assert_eq!(ulid.timestamp(), 1_350_508_407_000);
+    assert_eq!(ulid.to_object_id().unwrap(), object_id);
+
+    let mut next = object_id;
+    next[11] += 1;
+    assert!(Ulid::from_object_id(&next) > ulid);
+
+    let ulid = Ulid::new(1_350_508_407_999, || 0xFF);
+    let converted = Ulid::from_object_id(&ulid.to_object_id().unwrap());
+    assert_eq!(converted.timestamp(), 1_350_508_407_000);
+    assert_eq!(
+        converted.0[6..],
+        [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0]
+    );
+    assert!(Ulid::new(4_294_967_296_000, || 0).to_object_id().is_err());
+}
+
+#[cfg(feature = "sqlx")]
+#[test]
+fn sqlx() {
+    use super::integrations::sqlx::decode_bytes;
+    use super::UlidBytea;
+    use sqlx::encode::IsNull;
+    use sqlx::mysql::MySql;
+    use sqlx::postgres::{PgArgumentBuffer, Postgres};
+    use sqlx::sqlite::Sqlite;
+    use sqlx::{Encode, Type};
+
+    let ulid = Ulid::new(1_469_918_176_385, rand::random);
+    assert_eq!(decode_bytes(&ulid.0).unwrap(), ulid);
+    assert_eq!(decode_bytes(&ulid.marshal()).unwrap(), ulid);
+    assert!(decode_bytes(&ulid.0[1..]).is_err());
+
+    let mut buf = PgArgumentBuffer::default();
+    assert!(matches!(
+        Encode::<Postgres>::encode_by_ref(&ulid, &mut buf),
+        Ok(IsNull::No)
+    ));
+    assert_eq!(&buf[..], &ulid.0);
+
+    // UlidBytea binds the same 16 bytes as a bytea, so either column takes one
+    let mut buf = PgArgumentBuffer::default();
+    assert!(matches!(
+        Encode::<Postgres>::encode_by_ref(&UlidBytea(ulid), &mut buf),
+        Ok(IsNull::No)
+    ));
+    assert_eq!(&buf[..], &ulid.0);
+    let uuid = <Ulid as Type<Postgres>>::type_info();
+    let bytea = <Vec<u8> as Type<Postgres>>::type_info();
+    assert_eq!(<UlidBytea as Type<Postgres>>::type_info(), bytea);
+    assert_ne!(uuid, bytea);
+    for ty in &[&uuid, &bytea] {
+        assert!(<Ulid as Type<Postgres>>::compatible(ty));
+        assert!(<UlidBytea as Type<Postgres>>::compatible(ty));
+    }
+    assert_eq!(Ulid::from(UlidBytea::from(ulid)), ulid);
+
+    assert!(<Ulid as Type<Postgres>>::compatible(&<Vec<u8> as Type<
+        Postgres,
+    >>::type_info()));
+    assert!(!<Ulid as Type<Postgres>>::compatible(&<String as Type<
+        Postgres,
+    >>::type_info()));
+    assert!(<Ulid as Type<MySql>>::compatible(&<String as Type<
+        MySql,
+    >>::type_info()));
+    assert!(<Ulid as Type<Sqlite>>::compatible(&<String as Type<
+        Sqlite,
+    >>::type_info()));
+}
+
+#[cfg(feature = "diesel")]
+#[test]
+fn diesel() {
+    use diesel::deserialize::FromSql;
+    use diesel::expression::AsExpression;
+    use diesel::pg::Pg;
+    use diesel::serialize::ToSql;
+    use diesel::sql_types::{Binary, Uuid};
+
+    fn assert_sql<T, DB>()
+    where
+        DB: diesel::backend::Backend,
+        Ulid: ToSql<T, DB> + FromSql<T, DB> + AsExpression<T>,
+        T: diesel::sql_types::SqlType + diesel::expression::TypedExpressionType,
+    {
+    }
+
+    assert_sql::<Binary, Pg>();
+    assert_sql::<Uuid, Pg>();
+
+    let ulid = Ulid::new(1_469_918_176_385, || 0);
+    let query = diesel::select(AsExpression::<Uuid>::as_expression(ulid));
+    assert!(diesel::debug_query::<Pg, _>(&query)
+        .to_string()
+        .starts_with("SELECT $1"));
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn postgres() {
+    use postgres_types::{FromSql, ToSql, Type};
+
+    let ulid = Ulid::new(1_469_918_176_385, rand::random);
+
+    let mut buf = bytes::BytesMut::new();
+    ulid.to_sql_checked(&Type::UUID, &mut buf).unwrap();
+    assert_eq!(&buf[..], &ulid.0);
+    assert_eq!(Ulid::from_sql(&Type::BYTEA, &buf).unwrap(), ulid);
+
+    assert!(<Ulid as ToSql>::accepts(&Type::BYTEA));
+    assert!(!<Ulid as FromSql>::accepts(&Type::TEXT));
+    assert!(ulid.to_sql_checked(&Type::TEXT, &mut buf).is_err());
+    assert!(Ulid::from_sql(&Type::UUID, &ulid.0[1..]).is_err());
+}
+
+#[cfg(feature = "rusqlite")]
+#[test]
+fn rusqlite() {
+    use super::UlidText;
+
+    let conn = rusqlite::Connection::open_in_memory().unwrap();
+    conn.execute_batch("CREATE TABLE t (blob BLOB, text TEXT)")
+        .unwrap();
+
+    let ulid = Ulid::new(1_469_918_176_385, rand::random);
+    conn.execute("INSERT INTO t VALUES (?1, ?2)", (ulid, UlidText(ulid)))
+        .unwrap();
+
+    let (blob, text): (Vec<u8>, String) = conn
+        .query_row("SELECT blob, text FROM t", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(blob, ulid.0);
+    assert_eq!(text, ulid.to_string());
+
+    let (blob, text): (UlidText, Ulid) = conn
+        .query_row("SELECT blob, text FROM t", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(blob, UlidText(ulid));
+    assert_eq!(text, ulid);
+
+    assert!(conn
+        .query_row("SELECT x'0102'", [], |row| row.get::<_, Ulid>(0))
+        .is_err());
+}
+
+#[cfg(feature = "sea-orm")]
+#[test]
+fn sea_orm() {
+    use sea_orm::sea_query::{ColumnType, Nullable, Value, ValueType};
+    use sea_orm::{ActiveValue, IntoActiveValue, TryFromU64};
+
+    let ulid = Ulid::new(1_469_918_176_385, rand::random);
+
+    let value = Value::from(ulid);
+    assert_eq!(value, Value::Bytes(Some(Box::new(ulid.0.to_vec()))));
+    assert_eq!(<Ulid as ValueType>::try_from(value).unwrap(), ulid);
+    assert!(<Ulid as ValueType>::try_from(Ulid::null()).is_err());
+    assert!(<Ulid as ValueType>::try_from(Value::Bytes(Some(Box::new(vec![1, 2])))).is_err());
+    assert_eq!(Ulid::column_type(), ColumnType::Binary(16));
+
+    assert!(Ulid::try_from_u64(1).is_err());
+    assert_eq!(ulid.into_active_value(), ActiveValue::Set(ulid));
+    assert_eq!(Some(ulid).into_active_value(), ActiveValue::Set(Some(ulid)));
+}
+
+#[cfg(feature = "bson")]
+#[test]
+fn bson() {
+    use bson::spec::BinarySubtype;
+    use bson::{doc, Binary, Bson};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Event {
+        #[serde(rename = "_id", with = "super::bson::binary")]
+        id: Ulid,
+        #[serde(with = "super::bson::string")]
+        parent: Ulid,
+    }
+
+    let event = Event {
+        id: Ulid::new(1_469_918_176_385, rand::random),
+        parent: Ulid::new(1_469_918_176_384, rand::random),
+    };
+
+    let document = bson::to_document(&event).unwrap();
+    assert_eq!(
+        document,
+        doc! {
+            "_id": Binary { subtype: BinarySubtype::Uuid, bytes: event.id.0.to_vec() },
+            "parent": event.parent.to_string(),
+        }
+    );
+    assert_eq!(bson::from_document::<Event>(document).unwrap(), event);
+
+    let document = doc! { "_id": Bson::Binary(Binary { subtype: BinarySubtype::Md5, bytes: event.id.0.to_vec() }), "parent": event.parent.to_string() };
+    assert!(bson::from_document::<Event>(document).is_err());
+}
+
+#[cfg(feature = "redis")]
+#[test]
+fn redis() {
+    use redis::{FromRedisValue, ToRedisArgs, Value};
+
+    let ulid = Ulid::new(1_469_918_176_385, rand::random);
+    assert_eq!(ulid.to_redis_args(), vec![ulid.marshal().to_vec()]);
+
+    let value = Value::BulkString(ulid.marshal().to_vec());
+    assert_eq!(Ulid::from_redis_value(&value).unwrap(), ulid);
+    let value = Value::SimpleString(ulid.to_string());
+    assert_eq!(Ulid::from_redis_value(&value).unwrap(), ulid);
+
+    assert!(Ulid::from_redis_value(&Value::Nil).is_err());
+    assert!(Ulid::from_redis_value(&Value::BulkString(b"not a ulid".to_vec())).is_err());
+    assert_eq!(Option::<Ulid>::from_redis_value(&Value::Nil).unwrap(), None);
+}
+
+#[cfg(feature = "scylla")]
+#[test]
+fn scylla() {
+    use scylla::cluster::metadata::{ColumnType, NativeType};
+    use scylla::deserialize::value::DeserializeValue;
+    use scylla::deserialize::FrameSlice;
+    use scylla::serialize::value::SerializeValue;
+    use scylla::serialize::writers::CellWriter;
+
+    let ulid = Ulid::new(1_469_918_176_385, rand::random);
+
+    for typ in &[NativeType::Uuid, NativeType::Blob] {
+        let typ = ColumnType::Native(typ.clone());
+        let mut buf = Vec::new();
+        ulid.serialize(&typ, CellWriter::new(&mut buf)).unwrap();
+        // the value is prefixed with its length
+        assert_eq!(buf[..4], 16i32.to_be_bytes());
+        assert_eq!(buf[4..], ulid.0);
+        assert!(<Ulid as DeserializeValue>::type_check(&typ).is_ok());
+
+        let value = FrameSlice::new_borrowed(&buf[4..]);
+        assert_eq!(Ulid::deserialize(&typ, Some(value)).unwrap(), ulid);
+        let value = FrameSlice::new_borrowed(&buf[5..]);
+        assert!(Ulid::deserialize(&typ, Some(value)).is_err());
+    }
+
+    let text = ColumnType::Native(NativeType::Text);
+    assert!(ulid
+        .serialize(&text, CellWriter::new(&mut Vec::new()))
+        .is_err());
+    assert!(<Ulid as DeserializeValue>::type_check(&text).is_err());
+}
+
+#[cfg(feature = "async-graphql")]
+#[test]
+fn async_graphql() {
+    use async_graphql::{EmptyMutation, EmptySubscription, Object, ScalarType, Schema, Value};
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn timestamp(&self, id: Ulid) -> u64 {
+            id.timestamp()
+        }
+    }
+
+    let ulid = Ulid::new(1_469_918_176_385, rand::random);
+    assert_eq!(ulid.to_value(), Value::String(ulid.to_string()));
+    assert_eq!(<Ulid as ScalarType>::parse(ulid.to_value()).unwrap(), ulid);
+    assert!(!<Ulid as ScalarType>::is_valid(&Value::String("0".into())));
+    assert!(<Ulid as ScalarType>::parse(Value::Boolean(true)).is_err());
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert!(schema.sdl().contains("scalar Ulid"));
+}
+
+#[cfg(feature = "juniper")]
+#[test]
+fn juniper() {
+    use juniper::{
+        execute_sync, graphql_object, graphql_value, EmptyMutation, EmptySubscription, RootNode,
+        Variables,
+    };
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn id(id: Ulid) -> Ulid {
+            id
+        }
+    }
+
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    let ulid = Ulid::new(1_469_918_176_385, rand::random);
+    let query = format!("{{ id(id: \"{}\") }}", ulid);
+    let (res, errors) = execute_sync(&query, None, &schema, &Variables::new(), &()).unwrap();
+    assert!(errors.is_empty());
+    assert_eq!(res, graphql_value!({ "id": (ulid.to_string()) }));
+
+    assert!(execute_sync("{ id(id: \"0\") }", None, &schema, &Variables::new(), &()).is_err());
+}
+
+#[cfg(feature = "rocket")]
+mod rocket_routes {
+    use crate::{Ulid, UlidError};
+    use rocket::form::Form;
+    use rocket::{get, post, FromForm};
+
+    #[get("/orders/<id>")]
+    pub(super) fn order(id: Result<Ulid, UlidError>) -> Result<String, UlidError> {
+        Ok(id?.timestamp().to_string())
+    }
+
+    #[derive(FromForm)]
+    pub(super) struct Order {
+        id: Ulid,
+    }
+
+    #[post("/orders", data = "<order>")]
+    pub(super) fn create(order: Form<Order>) -> String {
+        order.id.to_string()
+    }
+}
+
+#[cfg(feature = "rocket")]
+#[test]
+fn rocket() {
+    use rocket::http::{ContentType, Status};
+    use rocket::local::blocking::Client;
+    use rocket::{routes, uri};
+    use rocket_routes::*;
+
+    let ulid = Ulid::new(1_469_918_176_385, rand::random);
+    assert_eq!(uri!(order(ulid)).to_string(), format!("/orders/{}", ulid));
+
+    let client = Client::tracked(rocket::build().mount("/", routes![order, create])).unwrap();
+
+    let res = client.get(format!("/orders/{}", ulid)).dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    assert_eq!(res.into_string().unwrap(), "1469918176385");
+
+    let res = client.get("/orders/not-a-ulid").dispatch();
+    assert_eq!(res.status(), Status::BadRequest);
+
+    let res = client
+        .post("/orders")
+        .header(ContentType::Form)
+        .body(format!("id={}", ulid))
+        .dispatch();
+    assert_eq!(res.into_string().unwrap(), ulid.to_string());
+
+    let res = client
+        .post("/orders")
+        .header(ContentType::Form)
+        .body("id=0")
+        .dispatch();
+    assert_eq!(res.status(), Status::BadRequest);
+}
+
+#[test]
+fn from_str() {
+    let ulid: Ulid = "01ARYZ6S41TSV4RRFFQ69G5FAV".parse().unwrap();
+    assert_eq!(ulid.to_string(), "01ARYZ6S41TSV4RRFFQ69G5FAV");
+    assert!(matches!(
+        "01ARYZ6S41TSV4RRFFQ69G5FAI".parse::<Ulid>(),
+        Err(UlidError::InvalidCharacter { .. })
+    ));
+}
+
+#[cfg(feature = "clap")]
+#[test]
+fn clap() {
+    use clap::{value_parser, Arg, Command};
+
+    let cmd = Command::new("orders").arg(Arg::new("id").value_parser(value_parser!(Ulid)));
+
+    let matches = cmd
+        .clone()
+        .try_get_matches_from(["orders", "01ARYZ6S41TSV4RRFFQ69G5FAV"])
+        .unwrap();
+    assert_eq!(
+        matches.get_one::<Ulid>("id").unwrap().to_string(),
+        "01ARYZ6S41TSV4RRFFQ69G5FAV"
+    );
+
+    let err = cmd
+        .clone()
+        .try_get_matches_from(["orders", "01ARYZ6S41TSV4RRFFQ69G5FAI"])
+        .unwrap_err();
+    assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation);
+    assert!(err
+        .to_string()
+        .contains("invalid character 'I' at position 25"));
+
+    let err = cmd.try_get_matches_from(["orders", "01ARYZ"]).unwrap_err();
+    assert!(err.to_string().contains("expected 26 characters, found 6"));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn rand_distribution() {
+    use rand::{Rng, SeedableRng};
+
+    let before = crate::clock::now_millis();
+    let ulid: Ulid = rand::thread_rng().gen();
+    assert!(ulid.timestamp() >= before);
+    assert!(ulid.timestamp() <= crate::clock::now_millis());
+
+    let mut a = rand::rngs::StdRng::seed_from_u64(7);
+    let mut b = rand::rngs::StdRng::seed_from_u64(7);
+    let (x, y): (Ulid, Ulid) = (a.gen(), b.gen());
+    assert_eq!(x.marshal()[10..], y.marshal()[10..]);
+}
+
+#[cfg(feature = "fake")]
+#[test]
+fn fake() {
+    use fake::rand::rngs::StdRng;
+    use fake::rand::SeedableRng;
+    use fake::{Dummy, Fake, Faker};
+
+    #[derive(Dummy)]
+    struct Order {
+        id: Ulid,
+        customer: Option<Ulid>,
+    }
+
+    let order: Order = Faker.fake();
+    assert_ne!(Some(order.id), order.customer);
+
+    let a: Ulid = Faker.fake_with_rng(&mut StdRng::seed_from_u64(7));
+    let b: Ulid = Faker.fake_with_rng(&mut StdRng::seed_from_u64(7));
+    assert_eq!(a, b);
+}
+
+#[test]
+fn cross_type_eq() {
+    let ulid = Ulid::unmarshal("01ARYZ6S41TSV4RRFFQ69G5FAV").unwrap();
+
+    assert_eq!(ulid, "01ARYZ6S41TSV4RRFFQ69G5FAV");
+    assert_eq!("01ARYZ6S41TSV4RRFFQ69G5FAV", ulid);
+    assert_eq!(ulid, *"01ARYZ6S41TSV4RRFFQ69G5FAV");
+    assert_ne!(ulid, "01ARYZ6S41TSV4RRFFQ69G5FAW");
+    assert_ne!(ulid, "not a ulid");
+
+    assert_eq!(ulid, <[u8; 16]>::from(ulid));
+    assert_eq!(<[u8; 16]>::from(ulid), ulid);
+    assert_ne!(ulid, [0; 16]);
+
+    assert_eq!(ulid, u128::from(ulid));
+    assert_eq!(u128::from(ulid), ulid);
+    assert_ne!(ulid, 0u128);
+}
+
+#[test]
+fn parse_error_detail() {
+    assert_eq!(
+        Ulid::unmarshal("01ARYZ6S41TSV4RRFFQ69G5FA"),
+        Err(UlidError::InvalidLength { len: 25 })
+    );
+    assert_eq!(
+        Ulid::unmarshal("01ARYZ6S41TSV4RRFFQ69G5FAVV"),
+        Err(UlidError::InvalidLength { len: 27 })
+    );
+    assert_eq!(
+        Ulid::unmarshal("01ARYZ6S41TSU4RRFFQ69G5FAV"),
+        Err(UlidError::InvalidCharacter {
+            index: 12,
+            byte: b'U'
+        })
+    );
+    assert_eq!(
+        Ulid::unmarshal("01ARYZ6S41TSV4RRFFQ69G5FUU"),
+        Err(UlidError::InvalidCharacter {
+            index: 24,
+            byte: b'U'
+        })
+    );
+
+    assert_eq!(
+        UlidError::InvalidLength { len: 25 }.to_string(),
+        "invalid length 25 for unmarshal"
+    );
+    assert_eq!(
+        UlidError::InvalidCharacter {
+            index: 12,
+            byte: b'U'
+        }
+        .to_string(),
+        "invalid character 'U' at index 12 encountered while parsing"
+    );
+    assert_eq!(
+        UlidError::InvalidCharacter {
+            index: 0,
+            byte: 0xC3
+        }
+        .to_string(),
+        "invalid byte 0xC3 at index 0 encountered while parsing"
+    );
+}
+
+#[test]
+fn error_variants() {
+    use core::convert::TryFrom;
+
+    assert_eq!(
+        Ulid::try_from(&[0u8; 15][..]),
+        Err(UlidError::InvalidBufferLength { len: 15 })
+    );
+    assert_eq!(
+        UlidError::InvalidBufferLength { len: 15 }.to_string(),
+        "invalid buffer length 15, expected 16 bytes"
+    );
+
+    let mut generator = Generator::new();
+    assert_eq!(
+        generator.create(1 << 48, || 0),
+        Err(UlidError::InvalidTimestamp)
+    );
+    assert!(generator.create((1 << 48) - 1, || 0).is_ok());
+}
+
+#[test]
+fn unmarshal_with() {
+    let ulid = Ulid::unmarshal("01ARYZ6S41TSV4RRFFQ69G5FAV").unwrap();
+
+    for strictness in [
+        Strictness::CanonicalOnly,
+        Strictness::Strict,
+        Strictness::Lenient,
+    ] {
+        assert_eq!(
+            Ulid::unmarshal_with("01ARYZ6S41TSV4RRFFQ69G5FAV", strictness),
+            Ok(ulid)
+        );
+        assert_eq!(
+            Ulid::unmarshal_with("81ARYZ6S41TSV4RRFFQ69G5FAV", strictness),
+            Err(UlidError::InvalidCharacter {
+                index: 0,
+                byte: b'8'
+            })
+        );
+        assert_eq!(
+            Ulid::unmarshal_with("01ARYZ6S41TSV4RRFFQ69G5FA", strictness),
+            Err(UlidError::InvalidLength { len: 25 })
+        );
+    }
+
+    assert_eq!(
+        Ulid::unmarshal_with("01aryz6s41tsv4rrffq69g5fav", Strictness::CanonicalOnly),
+        Err(UlidError::InvalidCharacter {
+            index: 2,
+            byte: b'a'
+        })
+    );
+    assert_eq!(
+        Ulid::unmarshal_with("01aryz6s41tsv4rrffq69g5fav", Strictness::Strict),
+        Ok(ulid)
+    );
+
+    assert_eq!(
+        Ulid::unmarshal_with("O1ARYZ6S4ITSV4RRFFQ69G5FAV", Strictness::Strict),
+        Err(UlidError::InvalidCharacter {
+            index: 0,
+            byte: b'O'
+        })
+    );
+    assert_eq!(
+        Ulid::unmarshal_with("O1ARYZ6S4ITSV4RRFFQ69G5FAV", Strictness::Lenient),
+        Ok(ulid)
+    );
+    assert_eq!(
+        Ulid::unmarshal_with("01ARYZ6S4lTSV4RRFFQ69G5FAu", Strictness::Lenient),
+        Err(UlidError::InvalidCharacter {
+            index: 25,
+            byte: b'u'
+        })
+    );
+}
+
+#[test]
+fn parse_many() {
+    let buf = b"01ARYZ6S41TSV4RRFFQ69G5FAV\r\n\n 01ARYZ6S41TSV4RRFFQ69G5FAU\n01ARYZ6S41\n7ZZZZZZZZZZZZZZZZZZZZZZZZZ";
+    let parsed: Vec<_> = Ulid::parse_many(buf, b'\n').collect();
+
+    assert_eq!(
+        parsed,
+        vec![
+            (
+                0,
+                Ok(Ulid::unmarshal("01ARYZ6S41TSV4RRFFQ69G5FAV").unwrap())
+            ),
+            (
+                30,
+                Err(UlidError::InvalidCharacter {
+                    index: 25,
+                    byte: b'U'
+                })
+            ),
+            (57, Err(UlidError::InvalidLength { len: 10 })),
+            (68, Ok(Ulid::from(u128::MAX))),
+        ]
+    );
+    assert_eq!(buf[30 + 25], b'U');
+
+    let ids: Result<Vec<_>, _> = Ulid::parse_many(
+        b"01ARYZ6S41TSV4RRFFQ69G5FAV, 7ZZZZZZZZZZZZZZZZZZZZZZZZZ,",
+        b',',
+    )
+    .map(|(_, ulid)| ulid)
+    .collect();
+    assert_eq!(ids.unwrap().len(), 2);
+
+    assert_eq!(Ulid::parse_many(b"", b'\n').next(), None);
+    assert_eq!(Ulid::parse_many(b"\n \n", b'\n').next(), None);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn stream() {
+    use super::{UlidReader, UlidWriter};
+    use std::io::ErrorKind;
+
+    let ulids: Vec<Ulid> = (0..3u128).map(Ulid::from).collect();
+
+    let mut writer = UlidWriter::new(Vec::new());
+    writer.write(&ulids[0]).unwrap();
+    writer.write(&ulids[1]).unwrap();
+    let buf = writer.into_inner();
+    assert_eq!(buf.len(), 32);
+
+    let mut reader = UlidReader::new(&buf[..]);
+    assert_eq!(reader.read().unwrap(), Some(ulids[0]));
+    assert_eq!(reader.read().unwrap(), Some(ulids[1]));
+    assert_eq!(reader.read().unwrap(), None);
+
+    let read: Vec<Ulid> = UlidReader::new(&buf[..]).collect::<Result<_, _>>().unwrap();
+    assert_eq!(read, ulids[..2]);
+
+    let err = UlidReader::new(&buf[..20]).nth(1).unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+    let mut writer = UlidWriter::new(Vec::new());
+    writer.write_batch(&ulids).unwrap();
+    writer.write_batch(&[]).unwrap();
+    let buf = writer.into_inner();
+    assert_eq!(buf.len(), 4 + 48 + 4);
+
+    let mut reader = UlidReader::new(&buf[..]);
+    assert_eq!(reader.read_batch().unwrap(), ulids);
+    assert!(reader.read_batch().unwrap().is_empty());
+    assert_eq!(
+        reader.read_batch().unwrap_err().kind(),
+        ErrorKind::UnexpectedEof
+    );
+
+    let err = UlidReader::new(&[0, 0, 0, 2][..]).read_batch().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn write_to() {
+    use core::fmt::{self, Write};
+
+    /// a fixed buffer, as used without an allocator
+    struct Buf {
+        bytes: [u8; 32],
+        len: usize,
+    }
+
+    impl Write for Buf {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            self.bytes
+                .get_mut(self.len..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    let ulid = Ulid::unmarshal("01ARYZ6S41TSV4RRFFQ69G5FAV").unwrap();
+    let mut buf = Buf {
+        bytes: [0; 32],
+        len: 0,
+    };
+    ulid.write_to(&mut buf).unwrap();
+    assert_eq!(&buf.bytes[..buf.len], b"01ARYZ6S41TSV4RRFFQ69G5FAV");
+    assert!(ulid.write_to(&mut buf).is_err());
+
+    let mut s = String::new();
+    ulid.write_to(&mut s).unwrap();
+    assert_eq!(s, ulid.to_string());
+}
+
+#[cfg(feature = "embedded")]
+#[test]
+fn heapless_string() {
+    let ulid = Ulid::unmarshal("01ARYZ6S41TSV4RRFFQ69G5FAV").unwrap();
+    let s = heapless::String::<26>::from(ulid);
+    assert_eq!(s.as_str(), "01ARYZ6S41TSV4RRFFQ69G5FAV");
+}
+
+#[cfg(feature = "embedded-hal")]
+#[test]
+fn hal_rng_entropy() {
+    use super::HalRngEntropy;
+
+    /// a peripheral counting up from its state
+    struct Trng(u8);
+
+    impl embedded_hal::blocking::rng::Read for Trng {
+        type Error = ();
+
+        fn read(&mut self, buffer: &mut [u8]) -> Result<(), ()> {
+            for byte in buffer {
+                self.0 += 1;
+                *byte = self.0;
+            }
+            Ok(())
+        }
+    }
+
+    let mut trng = Trng(0);
+    let ulid = Ulid::new(1_469_918_176_385, HalRngEntropy(&mut trng));
+    assert_eq!(ulid.0[6..], [1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+
+    let mut generator = Generator::new();
+    let ulid = generator
+        .create(1_469_918_176_385, HalRngEntropy(&mut trng))
+        .unwrap();
+    assert_eq!(ulid.0[6], 11);
+    assert_eq!(trng.0, 20);
+}
+
+#[test]
+fn epoch() {
+    use super::EpochClock;
+
+    const EPOCH_2020: u64 = 1_577_836_800_000;
+
+    let clock = EpochClock::with_clock(EPOCH_2020, || EPOCH_2020 + 42);
+    assert_eq!(clock.epoch(), EPOCH_2020);
+
+    let mut generator = Generator::with_clock(clock);
+    let ulid = generator.now(|| 7).unwrap();
+    assert_eq!(ulid.timestamp(), 42);
+    assert_eq!(ulid.timestamp_with_epoch(EPOCH_2020), Some(EPOCH_2020 + 42));
+    assert_eq!(ulid.timestamp_with_epoch(u64::MAX - 41), None);
+
+    let unix = ulid.rebase_epoch(EPOCH_2020, 0).unwrap();
+    assert_eq!(unix.timestamp(), EPOCH_2020 + 42);
+    assert_eq!(unix.0[6..], ulid.0[6..]);
+    assert_eq!(unix.rebase_epoch(0, EPOCH_2020), Some(ulid));
+
+    assert_eq!(ulid.rebase_epoch(0, EPOCH_2020), None);
+    assert_eq!(Ulid::from(u128::MAX).rebase_epoch(1, 0), None);
+
+    let before = EpochClock::with_clock(EPOCH_2020, || 5);
+    assert_eq!(Ulid::now_with_clock(&before, || 0).timestamp(), 0);
+}
+
+#[test]
+fn ulid64() {
+    use super::Ulid64;
+
+    let ulid = Ulid64::new(42, || 0xAB);
+    assert_eq!(ulid.timestamp(), 42);
+    assert_eq!(ulid.unix_timestamp(), Ulid64::EPOCH + 42);
+    assert_eq!(ulid.entropy(), 0xABABAB);
+    assert_eq!(u64::from(ulid), 42 << 24 | 0xABABAB);
+
+    let s = ulid.to_string();
+    assert_eq!(s.len(), 13);
+    assert_eq!(s.parse::<Ulid64>(), Ok(ulid));
+    assert_eq!(Ulid64::unmarshal(ulid.marshal()), Ok(ulid));
+
+    let max = Ulid64::from(u64::MAX);
+    assert_eq!(max.to_string(), "FZZZZZZZZZZZZ");
+    assert_eq!(Ulid64::unmarshal("FZZZZZZZZZZZZ"), Ok(max));
+    assert_eq!(
+        Ulid64::unmarshal("GZZZZZZZZZZZZ"),
+        Err(UlidError::InvalidCharacter {
+            index: 0,
+            byte: b'G'
+        })
+    );
+    assert!(Ulid64::unmarshal("01ARYZ6S41TSV").is_ok());
+    assert_eq!(
+        Ulid64::unmarshal("0000"),
+        Err(UlidError::InvalidLength { len: 4 })
+    );
+
+    let a = Ulid64::new(1, || 0xFF);
+    let b = Ulid64::new(2, || 0);
+    assert!(a < b);
+    assert!(a.to_string() < b.to_string());
+
+    let clock = || Ulid64::EPOCH + 1_000;
+    assert_eq!(Ulid64::now_with_clock(&clock, || 0).timestamp(), 1_000);
+    assert_eq!(Ulid64::from(<[u8; 8]>::from(ulid)), ulid);
+}
+
+#[test]
+fn is_valid() {
+    assert!(Ulid::is_valid("01ARYZ6S41TSV4RRFFQ69G5FAV"));
+    assert!(Ulid::is_valid("7ZZZZZZZZZZZZZZZZZZZZZZZZZ"));
+    assert!(Ulid::is_valid(b"00000000000000000000000000"));
+
+    assert!(!Ulid::is_valid("81ARYZ6S41TSV4RRFFQ69G5FAV"));
+    assert!(!Ulid::is_valid("01ARYZ6S41TSV4RRFFQ69G5FAU"));
+    assert!(!Ulid::is_valid("01aryz6s41tsv4rrffq69g5fav"));
+    assert!(!Ulid::is_valid("01ARYZ6S41TSV4RRFFQ69G5FA"));
+    assert!(!Ulid::is_valid(""));
+
+    for _ in 0..100 {
+        let s = Ulid::from(rand::random::<u128>()).to_string();
+        assert!(Ulid::is_valid(&s));
+    }
+}
+
+#[test]
+fn timestamp_from_str() {
+    let ulid = Ulid::new(1_469_918_176_385, rand::random);
+
+    assert_eq!(
+        Ulid::timestamp_from_str(ulid.to_string()),
+        Ok(1_469_918_176_385)
+    );
+    assert_eq!(
+        Ulid::timestamp_from_str(ulid.timestamp_prefix()),
+        Ok(1_469_918_176_385)
+    );
+    assert_eq!(
+        Ulid::timestamp_from_str("7ZZZZZZZZZZZZZZZZZZZZZZZZZ"),
+        Ok((1 << 48) - 1)
+    );
+
+    assert_eq!(
+        Ulid::timestamp_from_str("81ARYZ6S41TSV4RRFFQ69G5FAV"),
+        Err(UlidError::InvalidCharacter {
+            index: 0,
+            byte: b'8'
+        })
+    );
+    assert_eq!(
+        Ulid::timestamp_from_str("01ARYZ6SU1TSV4RRFFQ69G5FAV"),
+        Err(UlidError::InvalidCharacter {
+            index: 8,
+            byte: b'U'
+        })
+    );
+    assert_eq!(
+        Ulid::timestamp_from_str("01ARYZ6S41TSV"),
+        Err(UlidError::InvalidLength { len: 13 })
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn age() {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    let ulid = Ulid::new(1_469_918_176_385, || 0);
+    let at = UNIX_EPOCH + Duration::from_millis(1_469_918_176_385 + 1_500);
+    assert_eq!(ulid.age_at(at), Duration::from_millis(1_500));
+    assert_eq!(ulid.age_at(UNIX_EPOCH), Duration::ZERO);
+
+    assert!(ulid.age() > Duration::from_secs(3600));
+    assert!(ulid.age_at(SystemTime::now()) >= ulid.age() - Duration::from_secs(1));
+
+    let future = Ulid::new(u64::from(u32::MAX) << 16, || 0);
+    assert_eq!(future.age(), Duration::ZERO);
+}
+
+#[test]
+fn duration_since() {
+    use core::time::Duration;
+
+    let a = Ulid::new(1_469_918_176_385, rand::random);
+    let b = Ulid::new(1_469_918_178_000, rand::random);
+
+    assert_eq!(b.duration_since(&a), Duration::from_millis(1_615));
+    assert_eq!(a.duration_since(&b), Duration::ZERO);
+    assert_eq!(
+        b.checked_duration_since(&a),
+        Some(Duration::from_millis(1_615))
+    );
+    assert_eq!(a.checked_duration_since(&b), None);
+    assert_eq!(a.checked_duration_since(&a), Some(Duration::ZERO));
+
+    assert_eq!(b.millis_since(&a), 1_615);
+    assert_eq!(a.millis_since(&b), -1_615);
+    assert_eq!(
+        Ulid::from(u128::MAX).millis_since(&Ulid::from(0)),
+        (1 << 48) - 1
+    );
+}
+
+#[test]
+fn add_sub_duration() {
+    use core::time::Duration;
+
+    let ulid = Ulid::new(1_469_918_176_385, rand::random);
+    let later = ulid + Duration::from_secs(3600);
+    assert_eq!(later.timestamp(), 1_469_918_176_385 + 3_600_000);
+    assert_eq!(later.0[6..], ulid.0[6..]);
+    assert_eq!(later - Duration::from_secs(3600), ulid);
+
+    let mut moved = ulid;
+    moved += Duration::from_micros(1_999);
+    assert_eq!(moved.timestamp(), 1_469_918_176_386);
+    moved -= Duration::from_millis(1);
+    assert_eq!(moved, ulid);
+
+    assert_eq!(
+        ulid.checked_sub(Duration::from_millis(1_469_918_176_386)),
+        None
+    );
+    assert_eq!(
+        Ulid::from(u128::MAX).checked_add(Duration::from_millis(1)),
+        None
+    );
+    assert_eq!(ulid.checked_add(Duration::MAX), None);
+    assert_eq!(ulid.checked_add(Duration::ZERO), Some(ulid));
+}
+
+#[test]
+#[should_panic(expected = "overflow when adding duration to ulid")]
+fn add_duration_overflow() {
+    let _ = Ulid::from(u128::MAX) + core::time::Duration::from_millis(1);
+}
+
+#[test]
+fn sub_ulid() {
+    use core::time::Duration;
+
+    let a = Ulid::new(1_469_918_176_385, rand::random);
+    let b = Ulid::new(1_469_918_178_000, rand::random);
+
+    assert_eq!(b - a, Duration::from_millis(1_615));
+    assert_eq!(a - b, Duration::ZERO);
+    assert_eq!(a - a, Duration::ZERO);
+    assert_eq!((a + (b - a)).timestamp(), b.timestamp());
+}
+
+#[cfg(feature = "nightly")]
+#[test]
+fn step() {
+    use super::UlidRange;
+
+    let start = Ulid::from(u128::from(u64::MAX));
+    let end = Ulid::from(u128::from(u64::MAX) + 3);
+
+    assert_eq!(
+        (start..end).collect::<Vec<_>>(),
+        UlidRange::from(start..end).collect::<Vec<_>>()
+    );
+    assert_eq!((start..=end).next_back(), Some(end));
+    assert_eq!((start..end).size_hint(), (3, Some(3)));
+    assert_eq!((end..start).next(), None);
+}
+
+#[test]
+fn non_nil() {
+    use super::NonNilUlid;
+    use core::hash::{Hash, Hasher};
+    use core::mem::size_of;
+    use std::collections::hash_map::DefaultHasher;
+
+    assert_eq!(size_of::<Option<NonNilUlid>>(), size_of::<Ulid>());
+    assert_eq!(size_of::<Option<NonNilUlid>>(), 16);
+
+    assert_eq!(NonNilUlid::new(Ulid::from(0)), None);
+
+    let ulid = Ulid::new(1_469_918_176_385, rand::random);
+    let non_nil = NonNilUlid::new(ulid).unwrap();
+    assert_eq!(non_nil.get(), ulid);
+    assert_eq!(Ulid::from(non_nil), ulid);
+    assert_eq!(non_nil.to_string(), ulid.to_string());
+
+    let a = NonNilUlid::new(Ulid::from(1)).unwrap();
+    let b = NonNilUlid::new(Ulid::from(1 << 80)).unwrap();
+    assert!(a < b);
+
+    fn hash<T: Hash>(value: T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+    assert_eq!(hash(non_nil), hash(ulid));
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn bytemuck() {
+    let ulids = [Ulid::from(1), Ulid::from(u128::MAX)];
+
+    let bytes: &[u8] = bytemuck::cast_slice(&ulids);
+    assert_eq!(bytes.len(), 32);
+    assert_eq!(bytes[15], 1);
+    assert_eq!(bytes[16..], [0xFF; 16]);
+
+    let back: &[Ulid] = bytemuck::cast_slice(bytes);
+    assert_eq!(back, ulids);
+
+    assert_eq!(<Ulid as bytemuck::Zeroable>::zeroed(), Ulid::from(0));
+    assert!(bytemuck::try_cast_slice::<u8, Ulid>(&bytes[..17]).is_err());
+}
+
+#[cfg(feature = "zerocopy")]
+#[test]
+fn zerocopy() {
+    use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+    #[derive(FromBytes, IntoBytes, Unaligned, Immutable, KnownLayout)]
+    #[repr(C)]
+    struct Packet {
+        kind: u8,
+        id: Ulid,
+    }
+
+    let ulid = Ulid::from(0x0123_4567_89AB_CDEF_0123_4567_89AB_CDEF);
+    let mut buf = [0u8; 17];
+    buf[0] = 7;
+    buf[1..].copy_from_slice(ulid.as_bytes());
+
+    let packet = Packet::ref_from_bytes(&buf[..]).unwrap();
+    assert_eq!(packet.kind, 7);
+    assert_eq!(packet.id, ulid);
+    assert_eq!(packet.as_bytes(), buf);
+
+    assert_eq!(Ulid::read_from_bytes(&buf[1..]).unwrap(), ulid);
+    assert!(Ulid::ref_from_bytes(&buf[..]).is_err());
+}
+
+#[test]
+fn id() {
+    use super::Id;
+    use std::collections::HashSet;
+
+    struct User;
+    struct Order;
+
+    let ulid = Ulid::from(0x0123_4567_89AB_CDEF_0123_4567_89AB_CDEF);
+    let user: Id<User> = Id::from(ulid);
+
+    assert_eq!(user.ulid(), ulid);
+    assert_eq!(user, ulid);
+    assert_eq!(user.timestamp(), ulid.timestamp());
+    assert_eq!(user.to_string(), ulid.to_string());
+    assert_eq!(ulid.to_string().parse::<Id<User>>(), Ok(user));
+    assert!("not a ulid".parse::<Id<User>>().is_err());
+
+    let order: Id<Order> = user.cast();
+    assert_eq!(Ulid::from(order), ulid);
+
+    let ids: HashSet<Id<User>> = [user, user, Id::from(Ulid::from(1))].into();
+    assert_eq!(ids.len(), 2);
+    assert!(Id::<User>::from(Ulid::from(1)) < user);
+
+    assert_eq!(
+        std::mem::size_of::<Option<Id<User>>>(),
+        std::mem::size_of::<Option<Ulid>>()
+    );
+}
+
+#[cfg(feature = "rusqlite")]
+#[test]
+fn id_rusqlite() {
+    use super::Id;
+
+    struct User;
+
+    let conn = rusqlite::Connection::open_in_memory().unwrap();
+    conn.execute("CREATE TABLE users (id BLOB PRIMARY KEY)", [])
+        .unwrap();
+
+    let id: Id<User> = Id::from(Ulid::from(42));
+    conn.execute("INSERT INTO users (id) VALUES (?1)", [id])
+        .unwrap();
+
+    let back: Id<User> = conn
+        .query_row("SELECT id FROM users", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(back, id);
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derive_ulid_id() {
+    use super::UlidId;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, UlidId)]
+    struct OrderId(Ulid);
+
+    #[derive(Debug, PartialEq, UlidId)]
+    struct UserId {
+        ulid: Ulid,
+    }
+
+    let ulid = Ulid::from(0x0123_4567_89AB_CDEF_0123_4567_89AB_CDEF);
+    let s = ulid.to_string();
+
+    let order = OrderId::from(ulid);
+    assert_eq!(order.to_string(), s);
+    assert_eq!(s.parse::<OrderId>(), Ok(order));
+    assert_eq!(Ulid::from(order), ulid);
+    assert_eq!(order.as_ref(), &ulid);
+    assert!("not a ulid".parse::<OrderId>().is_err());
+
+    assert_eq!(s.parse::<UserId>(), Ok(UserId { ulid }));
+
+    #[cfg(feature = "serde")]
+    {
+        let json = serde_json::to_string(&order).unwrap();
+        assert_eq!(json, format!("\"{}\"", s));
+        assert_eq!(serde_json::from_str::<OrderId>(&json).unwrap(), order);
+    }
+
+    #[cfg(feature = "rusqlite")]
+    {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        let back: OrderId = conn
+            .query_row("SELECT ?1", [order], |row| row.get(0))
+            .unwrap();
+        assert_eq!(back, order);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    use super::Id;
+
+    let ulid = Ulid::from(0x0123_4567_89AB_CDEF_0123_4567_89AB_CDEF);
+    let json = serde_json::to_string(&ulid).unwrap();
+    assert_eq!(json, format!("\"{}\"", ulid));
+    assert_eq!(serde_json::from_str::<Ulid>(&json).unwrap(), ulid);
+    assert_eq!(serde_json::from_str::<Id<()>>(&json).unwrap(), ulid);
+
+    assert!(serde_json::from_str::<Ulid>("\"01ARZ3NDEKTSV4RRFFQ69G5FAVX\"").is_err());
+    assert!(serde_json::from_str::<Ulid>("42").is_err());
+}
+
+#[cfg(feature = "cipher")]
+#[test]
+fn cipher() {
+    use super::UlidCipher;
+
+    // FIPS-197 appendix C.1
+    let key = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+        0x0F,
+    ];
+    let cipher = UlidCipher::new(key);
+
+    let ulid = Ulid::from(0x0011_2233_4455_6677_8899_AABB_CCDD_EEFF);
+    let encrypted = cipher.encrypt(ulid);
+    assert_eq!(encrypted, 0x69C4_E0D8_6A7B_0430_D8CD_B780_70B4_C55A);
+    assert_eq!(cipher.decrypt(encrypted), ulid);
+
+    // consecutive Ulids from one millisecond share no visible structure
+    let next = Ulid::from(u128::from(ulid) + 1);
+    assert_ne!(
+        cipher.encrypt(next).timestamp(),
+        cipher.encrypt(ulid).timestamp()
+    );
+
+    assert_ne!(UlidCipher::new([0; 16]).encrypt(ulid), encrypted);
+    assert_eq!(format!("{:?}", cipher), "UlidCipher { .. }");
+}
+
+#[test]
+fn check_symbol() {
+    let ulid = Ulid::from(0x0123_4567_89AB_CDEF_0123_4567_89AB_CDEF);
+    let checked = ulid.marshal_checked();
+    assert_eq!(&checked[..26], &ulid.marshal()[..]);
+    assert_eq!(Ulid::unmarshal_checked(checked), Ok(ulid));
+
+    assert_eq!(Ulid::from(36).marshal_checked()[26], b'U');
+    assert_eq!(
+        Ulid::unmarshal_checked(Ulid::from(74).marshal_checked()),
+        Ok(Ulid::from(74))
+    );
+
+    // every single character substitution is caught
+    for index in 1..26 {
+        for &c in b"0123456789ABCDEFGHJKMNPQRSTVWXYZ" {
+            let mut typo = checked;
+            if typo[index] == c {
+                continue;
+            }
+            typo[index] = c;
+            assert_eq!(
+                Ulid::unmarshal_checked(typo),
+                Err(UlidError::InvalidCheckSymbol)
+            );
+        }
+    }
+
+    // in the first character too, where those above 7 would overflow
+    for &c in b"0123456789ABCDEFGHJKMNPQRSTVWXYZ" {
+        let mut typo = checked;
+        if typo[0] == c {
+            continue;
+        }
+        typo[0] = c;
+        let expected = if c > b'7' {
+            UlidError::InvalidCharacter { index: 0, byte: c }
+        } else {
+            UlidError::InvalidCheckSymbol
+        };
+        assert_eq!(Ulid::unmarshal_checked(typo), Err(expected));
+    }
+    assert_eq!(
+        Ulid::unmarshal_checked("814D2PF2DBSQQG28T5CY4TQKFFK"),
+        Err(UlidError::InvalidCharacter {
+            index: 0,
+            byte: b'8'
+        })
+    );
+
+    let mut swapped = checked;
+    swapped.swap(12, 13);
+    assert_eq!(
+        Ulid::unmarshal_checked(swapped),
+        Err(UlidError::InvalidCheckSymbol)
+    );
+
+    let mut bad = checked;
+    bad[26] = b'!';
+    assert_eq!(
+        Ulid::unmarshal_checked(bad),
+        Err(UlidError::InvalidCharacter {
+            index: 26,
+            byte: b'!'
+        })
+    );
+    assert_eq!(
+        Ulid::unmarshal_checked(ulid.marshal()),
+        Err(UlidError::InvalidLength { len: 26 })
+    );
+}
+
+#[cfg(feature = "signed")]
+#[test]
+fn signed() {
+    use super::UlidSigner;
+
+    let signer = UlidSigner::new(b"secret");
+    let ulid = Ulid::from(0x0123_4567_89AB_CDEF_0123_4567_89AB_CDEF);
+
+    let signed = signer.sign(ulid);
+    let s = signed.to_string();
+    assert_eq!(s.len(), 42);
+    assert!(s.starts_with(&ulid.to_string()));
+    assert_eq!(signed.ulid(), ulid);
+    assert_eq!(signer.verify(&s), Ok(ulid));
+    assert_eq!(signer.sign(ulid), signed);
+
+    assert_eq!(
+        UlidSigner::new(b"other").verify(&s),
+        Err(UlidError::InvalidSignature)
+    );
+
+    // a different Ulid with the tag of another
+    let forged = format!("{}{}", Ulid::from(1), &s[26..]);
+    assert_eq!(signer.verify(forged), Err(UlidError::InvalidSignature));
+
+    let mut bad = s.clone().into_bytes();
+    bad[30] = b'U';
+    assert_eq!(
+        signer.verify(&bad),
+        Err(UlidError::InvalidCharacter {
+            index: 30,
+            byte: b'U'
+        })
+    );
+    // the same Ulid if the overflowing bits were dropped
+    let mut overflowing = s.clone().into_bytes();
+    overflowing[0] = b'8';
+    assert_eq!(
+        signer.verify(&overflowing),
+        Err(UlidError::InvalidCharacter {
+            index: 0,
+            byte: b'8'
+        })
+    );
+    assert_eq!(
+        signer.verify(&s[..41]),
+        Err(UlidError::InvalidLength { len: 41 })
+    );
+}
+
+#[test]
+fn display_redacted() {
+    let ulid = Ulid::new(1_469_918_176_385, rand::random);
+
+    assert_eq!(
+        ulid.display_redacted().to_string(),
+        "01ARYZ6S41-****************"
+    );
+    assert_eq!(
+        format!("{:?}", ulid.display_redacted()),
+        "01ARYZ6S41-****************"
+    );
+}
+
+#[cfg(feature = "valuable")]
+#[test]
+fn valuable() {
+    use valuable::{Valuable, Value, Visit};
+
+    #[derive(Default)]
+    struct Collect(Vec<String>);
+
+    impl Visit for Collect {
+        fn visit_value(&mut self, value: Value<'_>) {
+            if let Value::Structable(s) = value {
+                self.0.push(s.definition().name().to_string());
+                s.visit(self);
+            }
+        }
+
+        fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
+            for value in values {
+                if let Value::String(s) = value {
+                    self.0.push(s.to_string());
+                }
+            }
+        }
+    }
+
+    let ulid = Ulid::from(0x0123_4567_89AB_CDEF_0123_4567_89AB_CDEF);
+
+    let mut collect = Collect::default();
+    valuable::visit(&ulid, &mut collect);
+    assert_eq!(collect.0, ["Ulid".to_string(), ulid.to_string()]);
+
+    let mut collect = Collect::default();
+    valuable::visit(&super::Id::<()>::from(ulid), &mut collect);
+    assert_eq!(collect.0, ["Ulid".to_string(), ulid.to_string()]);
+
+    assert!(matches!(ulid.as_value(), Value::Structable(_)));
+}
+
+#[cfg(feature = "opentelemetry")]
+#[test]
+fn opentelemetry() {
+    use opentelemetry::trace::{
+        SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
+    };
+    use opentelemetry::Context;
+
+    let ulid = Ulid::from(0x0123_4567_89AB_CDEF_0123_4567_89AB_CDEF);
+    let trace_id = TraceId::from(ulid);
+    assert_eq!(trace_id.to_string(), "0123456789abcdef0123456789abcdef");
+    assert_eq!(Ulid::from(trace_id), ulid);
+
+    assert_eq!(Ulid::from_active_trace(), None);
+
+    let span_context = SpanContext::new(
+        trace_id,
+        SpanId::from(1),
+        TraceFlags::SAMPLED,
+        true,
+        TraceState::default(),
+    );
+    let _guard = Context::new()
+        .with_remote_span_context(span_context)
+        .attach();
+    assert_eq!(Ulid::from_active_trace(), Some(ulid));
+}
+
+#[cfg(feature = "tower")]
+#[test]
+fn tower() {
+    use super::MakeUlidRequestId;
+    use core::future::{ready, Ready};
+    use std::convert::Infallible;
+    use std::task::{Context, Poll};
+    use tower_http::request_id::{PropagateRequestIdLayer, RequestId, SetRequestIdLayer};
+    use tower_layer::Layer;
+    use tower_service::Service;
+
+    /// responds with the request ID it got
+    struct Echo;
+
+    impl Service<http::Request<()>> for Echo {
+        type Response = http::Response<Option<RequestId>>;
+        type Error = Infallible;
+        type Future = Ready<Result<Self::Response, Infallible>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: http::Request<()>) -> Self::Future {
+            ready(Ok(http::Response::new(
+                req.extensions().get::<RequestId>().cloned(),
+            )))
+        }
+    }
+
+    let make = MakeUlidRequestId::default();
+    let mut service = SetRequestIdLayer::x_request_id(make.clone())
+        .layer(PropagateRequestIdLayer::x_request_id().layer(Echo));
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let mut call = |req| runtime.block_on(service.call(req)).unwrap();
+
+    let first = call(http::Request::new(()));
+    let header = first.headers()["x-request-id"].to_str().unwrap();
+    let ulid = header.parse::<Ulid>().unwrap();
+    let seen = first.body().as_ref().unwrap().header_value();
+    assert_eq!(seen.to_str().unwrap().parse::<Ulid>(), Ok(ulid));
+
+    // clones share the generator
+    let mut other = SetRequestIdLayer::x_request_id(make).layer(Echo);
+    let second = other.call(http::Request::new(())).into_inner().unwrap();
+    let second = second.body().as_ref().unwrap().header_value().to_str();
+    assert!(second.unwrap().parse::<Ulid>().unwrap() > ulid);
+
+    let existing = http::Request::builder()
+        .header("x-request-id", "from-upstream")
+        .body(())
+        .unwrap();
+    let existing = call(existing);
+    assert_eq!(existing.headers()["x-request-id"], "from-upstream");
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn arrow() {
+    use super::arrow;
+    use arrow_array::{Array, FixedSizeBinaryArray, StringArray};
+
+    let ulids = [Ulid::from(1), Ulid::from(u128::MAX >> 3)];
+
+    let binary = arrow::to_binary_array(&ulids);
+    assert_eq!(binary.value_length(), 16);
+    assert_eq!(binary.value(1), &Ulid::from(u128::MAX >> 3).0[..]);
+    assert_eq!(
+        arrow::from_binary_array(&binary),
+        Ok(vec![Some(ulids[0]), Some(ulids[1])])
+    );
+    assert_eq!(arrow::to_binary_array(&[]).len(), 0);
+
+    let strings = arrow::to_string_array(&ulids);
+    assert_eq!(strings.value(0), ulids[0].to_string());
+    assert_eq!(
+        arrow::from_string_array(&strings),
+        Ok(vec![Some(ulids[0]), Some(ulids[1])])
+    );
+
+    let with_null = StringArray::from(vec![Some(ulids[0].to_string()), None]);
+    assert_eq!(
+        arrow::from_string_array(&with_null),
+        Ok(vec![Some(ulids[0]), None])
+    );
+    let invalid = StringArray::from(vec!["not a ulid"]);
+    assert_eq!(
+        arrow::from_string_array(&invalid),
+        Err(UlidError::InvalidLength { len: 10 })
+    );
+
+    let narrow = FixedSizeBinaryArray::try_from_iter([[0u8; 8]].iter()).unwrap();
+    assert_eq!(
+        arrow::from_binary_array(&narrow),
+        Err(UlidError::InvalidBufferLength { len: 8 })
+    );
+}
+
+#[cfg(feature = "prost")]
+#[test]
+fn prost() {
+    use super::prost::{self, UlidMessage};
+    use ::prost::Message;
+    use core::convert::TryFrom;
+
+    let ulid = Ulid::from(0x0123_4567_89AB_CDEF_0123_4567_89AB_CDEF);
+
+    let encoded = UlidMessage::from(ulid).encode_to_vec();
+    assert_eq!(encoded.len(), 18);
+    let decoded = UlidMessage::decode(&encoded[..]).unwrap();
+    assert_eq!(Ulid::try_from(decoded), Ok(ulid));
+
+    // the proto3 default has no Ulid
+    let empty = UlidMessage::decode(&[][..]).unwrap();
+    assert_eq!(
+        Ulid::try_from(&empty),
+        Err(UlidError::InvalidBufferLength { len: 0 })
+    );
+    assert_eq!(prost::decode_optional(&empty.value), Ok(None));
+
+    let bytes: Vec<u8> = ulid.into();
+    assert_eq!(prost::decode(&bytes), Ok(ulid));
+    assert_eq!(prost::decode_optional(&bytes), Ok(Some(ulid)));
+    assert_eq!(
+        prost::decode_optional(&bytes[..15]),
+        Err(UlidError::InvalidBufferLength { len: 15 })
+    );
+}
+
+#[cfg(feature = "flatbuffers")]
+#[test]
+fn flatbuffers() {
+    use flatbuffers::{
+        FlatBufferBuilder, ForwardsUOffset, Table, Vector, Verifier, VerifierOptions,
+    };
+
+    const VT_ID: u16 = 4;
+    const VT_PARENTS: u16 = 6;
+
+    let ulid = Ulid::from(0x0123_4567_89AB_CDEF_0123_4567_89AB_CDEF);
+    let parents = [Ulid::from(1), Ulid::from(2)];
+
+    let mut builder = FlatBufferBuilder::new();
+    // a leading byte, so the Ulids are not aligned to 16
+    builder.push(1u8);
+    let vector = builder.create_vector(&parents);
+    let start = builder.start_table();
+    builder.push_slot_always::<&Ulid>(VT_ID, &ulid);
+    builder.push_slot_always(VT_PARENTS, vector);
+    let root = builder.end_table(start);
+    builder.finish_minimal(root);
+    let buf = builder.finished_data();
+
+    let opts = VerifierOptions::default();
+    let mut verifier = Verifier::new(&opts, buf);
+    let pos = u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+    verifier
+        .visit_table(pos)
+        .and_then(|t| t.visit_field::<Ulid>("id", VT_ID, true))
+        .and_then(|t| t.visit_field::<ForwardsUOffset<Vector<Ulid>>>("parents", VT_PARENTS, true))
+        .unwrap()
+        .finish();
+
+    let table = unsafe { flatbuffers::root_unchecked::<Table>(buf) };
+    let id: &Ulid = unsafe { table.get::<Ulid>(VT_ID, None) }.unwrap();
+    assert_eq!(*id, ulid);
+
+    let read = unsafe { table.get::<ForwardsUOffset<Vector<Ulid>>>(VT_PARENTS, None) }.unwrap();
+    assert_eq!(read.iter().copied().collect::<Vec<_>>(), parents);
+}
+
+#[cfg(feature = "rmp")]
+#[test]
+fn rmp() {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Call {
+        #[serde(with = "super::rmp::ext")]
+        id: Ulid,
+    }
+
+    let call = Call {
+        id: Ulid::from(0x0123_4567_89AB_CDEF_0123_4567_89AB_CDEF),
+    };
+
+    let bytes = rmp_serde::to_vec(&call).unwrap();
+    // a one element array holding fixext 16 of type 0x55
+    assert_eq!(bytes[..3], [0x91, 0xD8, 0x55]);
+    assert_eq!(bytes[3..], call.id.0);
+    assert_eq!(rmp_serde::from_slice::<Call>(&bytes).unwrap(), call);
+
+    let mut other_type = bytes.clone();
+    other_type[2] = 0x01;
+    assert!(rmp_serde::from_slice::<Call>(&other_type).is_err());
+
+    let plain = rmp_serde::to_vec(&(call.id.to_string(),)).unwrap();
+    assert!(rmp_serde::from_slice::<Call>(&plain).is_err());
+}
+
+#[cfg(feature = "ciborium")]
+#[test]
+fn ciborium() {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Tagged(#[serde(with = "super::ciborium::tagged")] Ulid);
+
+    let ulid = Ulid::from(0x0123_4567_89AB_CDEF_0123_4567_89AB_CDEF);
+
+    let mut plain = Vec::new();
+    ::ciborium::into_writer(&ulid, &mut plain).unwrap();
+    // a byte string of length 16
+    assert_eq!(plain[0], 0x50);
+    assert_eq!(plain[1..], ulid.0);
+    assert_eq!(
+        ::ciborium::from_reader::<Ulid, _>(&plain[..]).unwrap(),
+        ulid
+    );
+
+    let mut tagged = Vec::new();
+    ::ciborium::into_writer(&Tagged(ulid), &mut tagged).unwrap();
+    // a four byte tag, then the byte string
+    assert_eq!(tagged[..5], [0xDA, 0x55, 0x4C, 0x49, 0x44]);
+    assert_eq!(tagged[5..], plain[..]);
+    assert_eq!(
+        ::ciborium::from_reader::<Tagged, _>(&tagged[..]).unwrap(),
+        Tagged(ulid)
+    );
+
+    // untagged byte strings are still accepted, other tags are not
+    assert_eq!(
+        ::ciborium::from_reader::<Tagged, _>(&plain[..]).unwrap(),
+        Tagged(ulid)
+    );
+    tagged[4] = 0x45;
+    assert!(::ciborium::from_reader::<Tagged, _>(&tagged[..]).is_err());
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn async_generator() {
+    use super::AsyncGenerator;
+    use core::sync::atomic::{AtomicU64, Ordering};
+
+    // the clock only moves forward every third read
+    let reads = AtomicU64::new(0);
+    let clock = || 1_469_918_176_385 + reads.fetch_add(1, Ordering::Relaxed) / 3;
+    let gen = AsyncGenerator::from(Generator::with_clock(clock));
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        let ulid = gen.now(|| 0xFF).await.unwrap();
+        assert_eq!(ulid.to_string(), "01ARYZ6S41ZZZZZZZZZZZZZZZZ");
+        let ulid = gen.now(|| 0xFF).await.unwrap();
+        assert_eq!(ulid.to_string(), "01ARYZ6S42ZZZZZZZZZZZZZZZZ");
+
+        let ulid = AsyncGenerator::new().generate().await.unwrap();
+        assert!(ulid.timestamp() > 1_469_918_176_386);
+    });
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn persistent_generator() {
+    use super::{FileStore, GeneratorStore, PersistentGenerator};
+
+    let path = std::env::temp_dir().join(format!("ulid-rs-test-{}", std::process::id()));
+    let mut store = FileStore::new(&path);
+    assert_eq!(store.load().unwrap(), None);
+
+    let mut gen = PersistentGenerator::open(store.clone()).unwrap();
+    let ulid = gen.create(1_469_918_176_385, || 0).unwrap();
+    assert_eq!(store.load().unwrap(), Some(ulid));
+
+    // a restart with the clock moved backwards
+    let mut gen = PersistentGenerator::open(store.clone()).unwrap();
+    assert_eq!(gen.last(), Some(ulid));
+    let next = gen.create(1_469_918_176_384, || 0).unwrap();
+    assert_eq!(next.to_string(), "01ARYZ6S410000000000000001");
+    assert_eq!(store.load().unwrap(), Some(next));
+
+    std::fs::write(&path, b"short").unwrap();
+    assert!(PersistentGenerator::open(store.clone()).is_err());
+    std::fs::remove_file(&path).unwrap();
+
+    let mut gen = PersistentGenerator::open(Some(next)).unwrap();
+    let ulid = gen.now(|| 0).unwrap();
+    assert!(ulid > next);
+    assert_eq!(gen.into_store(), Some(ulid));
+
+    let gen = Generator::new().resume_after(next);
+    assert_eq!(gen.last(), Some(next));
+}
+
+#[cfg(feature = "pool")]
+#[test]
+fn pool() {
+    use super::UlidPool;
+    use std::time::{Duration, Instant};
+
+    let pool = UlidPool::new(64);
+    assert_eq!(pool.capacity(), 64);
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while pool.len() < 64 {
+        assert!(Instant::now() < deadline, "pool was not filled");
+        std::thread::yield_now();
+    }
+
+    let ulids: Vec<_> = (0..64).map(|_| pool.pop().unwrap()).collect();
+    assert!(ulids.windows(2).all(|w| w[0] < w[1]));
+}
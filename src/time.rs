@@ -0,0 +1,118 @@
+//! durations between Ulids and their timestamps
+
+use core::convert::TryFrom;
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+use core::time::Duration;
+
+use crate::{Ulid, ENTROPY_MASK};
+
+impl Ulid {
+    /// returns the time elapsed since the timestamp, or zero for a Ulid from
+    /// the future
+    #[cfg(feature = "std")]
+    pub fn age(&self) -> Duration {
+        Duration::from_millis(crate::clock::now_millis().saturating_sub(self.timestamp()))
+    }
+
+    /// returns the time elapsed between the timestamp and `now`, or zero when
+    /// `now` is earlier
+    #[cfg(feature = "std")]
+    pub fn age_at(&self, now: std::time::SystemTime) -> Duration {
+        let created = std::time::UNIX_EPOCH + Duration::from_millis(self.timestamp());
+        now.duration_since(created).unwrap_or_default()
+    }
+
+    /// returns the time between the timestamps of `earlier` and this Ulid, or
+    /// zero when `earlier` is later, like `Instant::duration_since`
+    pub fn duration_since(&self, earlier: &Ulid) -> Duration {
+        self.checked_duration_since(earlier).unwrap_or_default()
+    }
+
+    /// returns the time between the timestamps of `earlier` and this Ulid, or
+    /// `None` when `earlier` is later
+    pub fn checked_duration_since(&self, earlier: &Ulid) -> Option<Duration> {
+        self.timestamp()
+            .checked_sub(earlier.timestamp())
+            .map(Duration::from_millis)
+    }
+
+    /// returns the milliseconds between the timestamps of `other` and this
+    /// Ulid, negative when `other` is later
+    pub fn millis_since(&self, other: &Ulid) -> i64 {
+        // timestamps are 48 bits, so the difference always fits
+        self.timestamp() as i64 - other.timestamp() as i64
+    }
+
+    /// returns the Ulid with its timestamp moved later by `duration`, in whole
+    /// milliseconds, keeping the entropy, or `None` if it does not fit in 48
+    /// bits
+    pub fn checked_add(&self, duration: Duration) -> Option<Ulid> {
+        let millis = u64::try_from(duration.as_millis()).ok()?;
+        self.with_timestamp(self.timestamp().checked_add(millis)?)
+    }
+
+    /// returns the Ulid with its timestamp moved earlier by `duration`, in
+    /// whole milliseconds, keeping the entropy, or `None` if it is before the
+    /// unix epoch
+    pub fn checked_sub(&self, duration: Duration) -> Option<Ulid> {
+        let millis = u64::try_from(duration.as_millis()).ok()?;
+        self.with_timestamp(self.timestamp().checked_sub(millis)?)
+    }
+
+    /// returns the Ulid with another timestamp and the same entropy, or `None`
+    /// if it does not fit in 48 bits
+    pub(crate) fn with_timestamp(&self, timestamp: u64) -> Option<Ulid> {
+        if timestamp >> 48 != 0 {
+            return None;
+        }
+
+        let entropy = u128::from(*self) & ENTROPY_MASK;
+        Some(Ulid::from(u128::from(timestamp) << 80 | entropy))
+    }
+}
+
+/// # Panics
+///
+/// if the timestamp does not fit in 48 bits, see `Ulid::checked_add`
+impl Add<Duration> for Ulid {
+    type Output = Ulid;
+
+    fn add(self, duration: Duration) -> Ulid {
+        self.checked_add(duration)
+            .expect("overflow when adding duration to ulid")
+    }
+}
+
+impl AddAssign<Duration> for Ulid {
+    fn add_assign(&mut self, duration: Duration) {
+        *self = *self + duration;
+    }
+}
+
+/// # Panics
+///
+/// if the timestamp is before the unix epoch, see `Ulid::checked_sub`
+impl Sub<Duration> for Ulid {
+    type Output = Ulid;
+
+    fn sub(self, duration: Duration) -> Ulid {
+        self.checked_sub(duration)
+            .expect("overflow when subtracting duration from ulid")
+    }
+}
+
+impl SubAssign<Duration> for Ulid {
+    fn sub_assign(&mut self, duration: Duration) {
+        *self = *self - duration;
+    }
+}
+
+/// the time between the timestamps, zero when `rhs` is later, like
+/// `Instant - Instant`, see `Ulid::millis_since` for a signed difference
+impl Sub<Ulid> for Ulid {
+    type Output = Duration;
+
+    fn sub(self, rhs: Ulid) -> Duration {
+        self.duration_since(&rhs)
+    }
+}
@@ -0,0 +1,152 @@
+//! a compact 64 bit Ulid, for keys that cannot afford 16 bytes
+
+use core::fmt;
+
+use crate::codec::{DECODING, ENCODING};
+use crate::{Clock, EntropySource, UlidError};
+
+/// a 64 bit Ulid, with a 40 bit timestamp in milliseconds since
+/// `Ulid64::EPOCH` followed by 24 bits of entropy
+///
+/// Its text form is 13 characters of Crockford's base32, whose first character
+/// is at most `F`. Like `Ulid`, it compares and sorts by time first. The 40 bit
+/// timestamp lasts for 34 years from the epoch, until 2054, and Ulid64s created
+/// in the same millisecond only have 2^24 values to avoid colliding.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ulid64(u64);
+
+/// the 24 bits of entropy
+const ENTROPY_MASK: u64 = (1 << 24) - 1;
+
+impl Ulid64 {
+    /// 2020-01-01T00:00:00Z, in milliseconds since the unix epoch, which
+    /// timestamps count from
+    pub const EPOCH: u64 = 1_577_836_800_000;
+
+    /// creates new Ulid64 from a timestamp in milliseconds since
+    /// `Ulid64::EPOCH` and a source of entropy
+    ///
+    /// Only the last 40 bits of the timestamp are kept, and the first 3 bytes
+    /// of entropy.
+    pub fn new<E>(timestamp: u64, mut entropy: E) -> Ulid64
+    where
+        E: EntropySource,
+    {
+        let mut bytes = [0; 10];
+        entropy.fill(&mut bytes);
+        let entropy = u64::from_be_bytes([0, 0, 0, 0, 0, bytes[0], bytes[1], bytes[2]]);
+
+        Ulid64(timestamp << 24 | entropy)
+    }
+
+    /// creates new Ulid64 from the current time and a source of entropy
+    #[cfg(feature = "std")]
+    pub fn now<E>(entropy: E) -> Ulid64
+    where
+        E: EntropySource,
+    {
+        Ulid64::now_with_clock(&crate::SystemClock, entropy)
+    }
+
+    /// creates new Ulid64 from the time of a custom clock and a source of
+    /// entropy
+    pub fn now_with_clock<C, E>(clock: &C, entropy: E) -> Ulid64
+    where
+        C: Clock,
+        E: EntropySource,
+    {
+        Ulid64::new(clock.now_millis().saturating_sub(Ulid64::EPOCH), entropy)
+    }
+
+    /// returns the timestamp, in milliseconds since `Ulid64::EPOCH`
+    pub fn timestamp(&self) -> u64 {
+        self.0 >> 24
+    }
+
+    /// returns the timestamp in milliseconds since the unix epoch
+    pub fn unix_timestamp(&self) -> u64 {
+        self.timestamp() + Ulid64::EPOCH
+    }
+
+    /// returns the 24 bits of entropy
+    pub fn entropy(&self) -> u32 {
+        (self.0 & ENTROPY_MASK) as u32
+    }
+
+    /// creates a 13 character string representation
+    pub fn marshal(&self) -> [u8; 13] {
+        let mut ans = [0; 13];
+
+        for (i, c) in ans.iter_mut().rev().enumerate() {
+            // the first character takes the 65th bit as well, which is zero
+            *c = ENCODING[((self.0 >> (5 * i)) & 31) as usize];
+        }
+
+        ans
+    }
+
+    /// unmarshals a 13 character string-like into a Ulid64
+    pub fn unmarshal<S>(s: S) -> Result<Ulid64, UlidError>
+    where
+        S: AsRef<[u8]>,
+    {
+        let s = s.as_ref();
+
+        if s.len() != 13 {
+            return Err(UlidError::InvalidLength { len: s.len() });
+        }
+
+        let mut ans = 0;
+        for (index, &byte) in s.iter().enumerate() {
+            let word = DECODING[byte as usize];
+
+            // the first character only has room for 4 bits
+            if word == 0xFF || (index == 0 && word > 15) {
+                return Err(UlidError::InvalidCharacter { index, byte });
+            }
+
+            ans = ans << 5 | u64::from(word);
+        }
+
+        Ok(Ulid64(ans))
+    }
+}
+
+impl From<u64> for Ulid64 {
+    fn from(n: u64) -> Self {
+        Ulid64(n)
+    }
+}
+
+impl From<Ulid64> for u64 {
+    fn from(u: Ulid64) -> Self {
+        u.0
+    }
+}
+
+impl From<[u8; 8]> for Ulid64 {
+    fn from(bytes: [u8; 8]) -> Self {
+        Ulid64(u64::from_be_bytes(bytes))
+    }
+}
+
+impl From<Ulid64> for [u8; 8] {
+    fn from(u: Ulid64) -> Self {
+        u.0.to_be_bytes()
+    }
+}
+
+impl core::str::FromStr for Ulid64 {
+    type Err = UlidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ulid64::unmarshal(s)
+    }
+}
+
+impl fmt::Display for Ulid64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // marshal only ever produces bytes from ENCODING, which is ASCII
+        f.write_str(core::str::from_utf8(&self.marshal()).unwrap())
+    }
+}
@@ -0,0 +1,213 @@
+use std::process::Command;
+
+fn ulid(args: &[&str]) -> (bool, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_ulid"))
+        .args(args)
+        .output()
+        .unwrap();
+
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn generate() {
+    let (success, stdout) = ulid(&["generate"]);
+    assert!(success);
+    assert_eq!(stdout.len(), 27);
+    assert!(ulid_rs::Ulid::unmarshal(stdout.trim_end()).is_ok());
+
+    assert!(!ulid(&["generate", "extra"]).0);
+    assert!(!ulid(&["unknown"]).0);
+}
+
+#[test]
+fn inspect() {
+    let (success, stdout) = ulid(&["inspect", "01ARYZ6S41TSV4RRFFQ69G5FAV"]);
+    assert!(success);
+    assert_eq!(
+        stdout,
+        "ulid:      01ARYZ6S41TSV4RRFFQ69G5FAV
+timestamp: 1469918176385
+datetime:  2016-07-30T22:36:16.385Z
+entropy:   d6764c61efb99302bd5b
+uuid:      01563df3-6481-d676-4c61-efb99302bd5b
+"
+    );
+
+    let (success, stdout) = ulid(&["inspect", "--json", "01ARYZ6S41TSV4RRFFQ69G5FAV"]);
+    assert!(success);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["timestamp"], 1_469_918_176_385u64);
+    assert_eq!(json["datetime"], "2016-07-30T22:36:16.385Z");
+
+    // Ulid::MAX is in the year 10889, past what RFC 3339 can represent
+    let (success, stdout) = ulid(&["inspect", "7ZZZZZZZZZZZZZZZZZZZZZZZZZ"]);
+    assert!(success);
+    assert!(stdout.contains("timestamp: 281474976710655\ndatetime:  -\n"));
+    let (success, stdout) = ulid(&["inspect", "--json", "7ZZZZZZZZZZZZZZZZZZZZZZZZZ"]);
+    assert!(success);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["datetime"], serde_json::Value::Null);
+
+    // the last millisecond of 9999 is still formatted
+    let (success, stdout) = ulid(&["inspect", "76EZ91ZPZZ0000000000000000"]);
+    assert!(success);
+    assert!(stdout.contains("datetime:  9999-12-31T23:59:59.999Z\n"));
+
+    assert!(!ulid(&["inspect", "01ARYZ6S41"]).0);
+    assert!(!ulid(&["inspect", "81ARYZ6S41TSV4RRFFQ69G5FAV"]).0);
+}
+
+#[test]
+fn generate_monotonic() {
+    let (success, stdout) = ulid(&["generate", "-n", "10000", "--monotonic"]);
+    assert!(success);
+
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 10000);
+    assert!(lines.windows(2).all(|w| w[0] < w[1]));
+
+    assert!(!ulid(&["generate", "-n", "many"]).0);
+}
+
+#[test]
+fn validate() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let run = |args: &[&str], input: &[u8]| {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_ulid"))
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        child.stdin.take().unwrap().write_all(input).unwrap();
+
+        let output = child.wait_with_output().unwrap();
+        (
+            output.status.success(),
+            String::from_utf8(output.stdout).unwrap(),
+        )
+    };
+
+    let input = b"01ARYZ6S41TSV4RRFFQ69G5FAV\n01ARYZ6S41\n  01ARYZ6S41TSV4RRFFQ69G5FAW\r\n01ARYZ6S41TSV4RRFFQ69G5FAU\n81ARZ3NDEKTSV4RRFFQ69G5FAV\nZZZZZZZZZZZZZZZZZZZZZZZZZZ\n";
+
+    assert_eq!(
+        run(&["validate"], input),
+        (
+            true,
+            "01ARYZ6S41TSV4RRFFQ69G5FAV\n01ARYZ6S41TSV4RRFFQ69G5FAW\n".to_string()
+        )
+    );
+    assert_eq!(
+        run(&["validate", "--report"], input),
+        (
+            false,
+            "line 2: invalid length 10 for unmarshal: 01ARYZ6S41\nline 4: invalid character 'U' at index 25 encountered while parsing: 01ARYZ6S41TSV4RRFFQ69G5FAU\nline 5: invalid character '8' at index 0 encountered while parsing: 81ARZ3NDEKTSV4RRFFQ69G5FAV\nline 6: invalid character 'Z' at index 0 encountered while parsing: ZZZZZZZZZZZZZZZZZZZZZZZZZZ\n".to_string()
+        )
+    );
+
+    // blank lines, such as one at the end of an export, are skipped
+    assert_eq!(
+        run(
+            &["validate", "--report"],
+            b"01ARYZ6S41TSV4RRFFQ69G5FAV\n\n01ARYZ6S41TSV4RRFFQ69G5FAW\n\n"
+        ),
+        (true, String::new())
+    );
+}
+
+#[test]
+fn convert() {
+    assert_eq!(
+        ulid(&["convert", "--to", "uuid", "01ARYZ6S41TSV4RRFFQ69G5FAV"]),
+        (true, "01563df3-6481-d676-4c61-efb99302bd5b\n".to_string())
+    );
+    assert_eq!(
+        ulid(&[
+            "convert",
+            "--from",
+            "uuid",
+            "01563DF3-6481-D676-4C61-EFB99302BD5B",
+            "01563df36481d6764c61efb99302bd5b"
+        ]),
+        (
+            true,
+            "01ARYZ6S41TSV4RRFFQ69G5FAV\n01ARYZ6S41TSV4RRFFQ69G5FAV\n".to_string()
+        )
+    );
+
+    assert!(!ulid(&["convert", "--to", "uuid", "01ARYZ6S41"]).0);
+    assert!(!ulid(&["convert", "--to", "uuid", "ZZZZZZZZZZZZZZZZZZZZZZZZZZ"]).0);
+    assert!(!ulid(&["convert", "--to", "ksuid", "01ARYZ6S41TSV4RRFFQ69G5FAV"]).0);
+}
+
+#[test]
+fn sort_and_ts() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let run = |args: &[&str], stdin: &[u8]| {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_ulid"))
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        child.stdin.take().unwrap().write_all(stdin).unwrap();
+
+        let output = child.wait_with_output().unwrap();
+        (
+            output.status.success(),
+            String::from_utf8(output.stdout).unwrap(),
+        )
+    };
+
+    let ids =
+        b"01BX5ZZKBKACTAV9WEVGEMMVRZ\n01ARYZ6S41TSV4RRFFQ69G5FAV\n\n01ARYZ6S41TSV4RRFFQ69G5FAA\n";
+    assert_eq!(
+        run(&["sort"], ids),
+        (
+            true,
+            "01ARYZ6S41TSV4RRFFQ69G5FAA\n01ARYZ6S41TSV4RRFFQ69G5FAV\n01BX5ZZKBKACTAV9WEVGEMMVRZ\n"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        run(&["ts"], b"01ARYZ6S41TSV4RRFFQ69G5FAV\n"),
+        (
+            true,
+            "01ARYZ6S41TSV4RRFFQ69G5FAV,2016-07-30T22:36:16.385Z\n".to_string()
+        )
+    );
+    assert_eq!(
+        run(&["ts", "--json"], b"01ARYZ6S41TSV4RRFFQ69G5FAV\n"),
+        (
+            true,
+            "{\"ulid\":\"01ARYZ6S41TSV4RRFFQ69G5FAV\",\"datetime\":\"2016-07-30T22:36:16.385Z\"}\n"
+                .to_string()
+        )
+    );
+
+    assert_eq!(
+        run(&["ts"], b"7ZZZZZZZZZZZZZZZZZZZZZZZZZ\n"),
+        (true, "7ZZZZZZZZZZZZZZZZZZZZZZZZZ,\n".to_string())
+    );
+    assert_eq!(
+        run(&["ts", "--json"], b"7ZZZZZZZZZZZZZZZZZZZZZZZZZ\n"),
+        (
+            true,
+            "{\"ulid\":\"7ZZZZZZZZZZZZZZZZZZZZZZZZZ\",\"datetime\":null}\n".to_string()
+        )
+    );
+
+    assert!(!run(&["sort"], b"01ARYZ6S41\n").0);
+    assert!(!run(&["sort"], b"81ARYZ6S41TSV4RRFFQ69G5FAV\n").0);
+    assert!(!run(&["ts"], b"ZZZZZZZZZZZZZZZZZZZZZZZZZZ\n").0);
+}
@@ -0,0 +1,48 @@
+/*
+ * Checks include/ulid.h against the library built with the `ffi` feature.
+ *
+ *   cargo rustc --release --lib --features ffi --crate-type staticlib
+ *   cc -std=c99 -Wall -Wextra -Werror -Iinclude tests/ffi.c \
+ *       target/release/libulid_rs.a -lpthread -ldl -lm -o target/ffi
+ *   target/ffi
+ */
+
+#include <assert.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "ulid.h"
+
+int main(void) {
+    ulid_t ulid;
+    ulid_t parsed;
+    char buf[27];
+    int ret;
+
+    /* the calls are kept out of assert, which -DNDEBUG compiles away */
+    ret = ulid_parse("01ARYZ6S41TSV4RRFFQ69G5FAV", ulid);
+    assert(ret == 0);
+    assert(ulid_timestamp(ulid) == 1469918176385ULL);
+    assert(ulid[0] == 0x01 && ulid[15] == 0x5B);
+
+    ulid_to_string(ulid, buf);
+    assert(strcmp(buf, "01ARYZ6S41TSV4RRFFQ69G5FAV") == 0);
+
+    memset(parsed, 0xAA, sizeof parsed);
+    ret = ulid_parse("81ARYZ6S41TSV4RRFFQ69G5FAV", parsed);
+    assert(ret == -1);
+    ret = ulid_parse("01ARYZ6S41", parsed);
+    assert(ret == -1);
+    assert(parsed[0] == 0xAA && parsed[15] == 0xAA);
+
+    ulid_new(ulid);
+    ulid_to_string(ulid, buf);
+    ret = ulid_parse(buf, parsed);
+    assert(ret == 0);
+    assert(memcmp(ulid, parsed, sizeof ulid) == 0);
+    assert(ulid_timestamp(ulid) > 1469918176385ULL);
+
+    (void)ret;
+    puts("ok");
+    return 0;
+}